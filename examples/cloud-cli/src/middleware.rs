@@ -25,7 +25,7 @@ pub struct AuthMiddleware {
 impl Middleware for AuthMiddleware {
     fn before(&self, ctx: &mut Context) -> KoralResult<()> {
         // Skip auth for "login" and "help" commands
-        if let Some(cmd) = ctx.args.get(0) {
+        if let Some(cmd) = ctx.args.first() {
             if cmd == "login" || cmd == "help" || cmd == "--help" || cmd == "-h" {
                 return Ok(());
             }
@@ -15,7 +15,7 @@ pub enum PipelinesCmd {
 
 impl Default for PipelinesCmd {
     fn default() -> Self {
-        Self::List(ListPipelinesCmd::default())
+        Self::List(ListPipelinesCmd)
     }
 }
 
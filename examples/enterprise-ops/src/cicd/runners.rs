@@ -11,7 +11,7 @@ pub enum RunnersCmd {
 
 impl Default for RunnersCmd {
     fn default() -> Self {
-        Self::Register(RegisterRunnerCmd::default())
+        Self::Register(RegisterRunnerCmd)
     }
 }
 
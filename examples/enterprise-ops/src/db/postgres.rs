@@ -17,7 +17,7 @@ pub enum PostgresCmd {
 
 impl Default for PostgresCmd {
     fn default() -> Self {
-        Self::List(ListInstancesCmd::default())
+        Self::List(ListInstancesCmd)
     }
 }
 
@@ -98,7 +98,7 @@ pub enum BackupsCmd {
 
 impl Default for BackupsCmd {
     fn default() -> Self {
-        Self::List(ListBackups::default())
+        Self::List(ListBackups)
     }
 }
 
@@ -11,7 +11,7 @@ pub enum RedisCmd {
 
 impl Default for RedisCmd {
     fn default() -> Self {
-        Self::Flush(FlushCacheCmd::default())
+        Self::Flush(FlushCacheCmd)
     }
 }
 
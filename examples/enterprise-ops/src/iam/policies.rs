@@ -11,7 +11,7 @@ pub enum PoliciesCmd {
 
 impl Default for PoliciesCmd {
     fn default() -> Self {
-        Self::Validate(ValidatePolicyCmd::default())
+        Self::Validate(ValidatePolicyCmd)
     }
 }
 
@@ -15,7 +15,7 @@ pub enum UsersCmd {
 
 impl Default for UsersCmd {
     fn default() -> Self {
-        Self::List(ListUsersCmd::default())
+        Self::List(ListUsersCmd)
     }
 }
 
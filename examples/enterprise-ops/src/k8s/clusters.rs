@@ -19,7 +19,7 @@ pub enum ClustersCmd {
 
 impl Default for ClustersCmd {
     fn default() -> Self {
-        Self::List(ListCmd::default())
+        Self::List(ListCmd)
     }
 }
 
@@ -17,7 +17,7 @@ pub enum NodePoolsCmd {
 
 impl Default for NodePoolsCmd {
     fn default() -> Self {
-        Self::List(ListPoolsCmd::default())
+        Self::List(ListPoolsCmd)
     }
 }
 
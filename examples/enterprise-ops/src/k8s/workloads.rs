@@ -18,7 +18,7 @@ pub enum WorkloadsCmd {
 
 impl Default for WorkloadsCmd {
     fn default() -> Self {
-        Self::List(ListWorkloadsCmd::default())
+        Self::List(ListWorkloadsCmd)
     }
 }
 
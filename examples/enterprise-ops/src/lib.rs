@@ -18,11 +18,11 @@ pub mod network;
 // --- Global Flags ---
 
 #[derive(Flag, Debug)]
-#[flag(name = "verbose", short = 'v', help = "Enable verbose output")]
+#[flag(name = "verbose", short = 'v', global, help = "Enable verbose output")]
 pub struct Verbose(bool);
 
 #[derive(Flag, Debug)]
-#[flag(name = "dry-run", help = "Preview changes without executing")]
+#[flag(name = "dry-run", global, help = "Preview changes without executing")]
 pub struct DryRun(bool);
 
 #[derive(Flag, Debug)]
@@ -30,6 +30,7 @@ pub struct DryRun(bool);
     name = "output",
     short = 'o',
     default = "text",
+    global,
     help = "Output format (text, json, yaml, table)"
 )]
 pub struct Output(OutputFormat);
@@ -38,6 +39,7 @@ pub struct Output(OutputFormat);
 #[flag(
     name = "profile",
     default = "default",
+    global,
     help = "Configuration profile to use"
 )]
 pub struct ProfileFlag(String);
@@ -11,7 +11,7 @@ pub enum LogsCmd {
 
 impl Default for LogsCmd {
     fn default() -> Self {
-        Self::Search(SearchLogsCmd::default())
+        Self::Search(SearchLogsCmd)
     }
 }
 
@@ -13,7 +13,7 @@ pub enum MetricsCmd {
 
 impl Default for MetricsCmd {
     fn default() -> Self {
-        Self::Query(QueryCmd::default())
+        Self::Query(QueryCmd)
     }
 }
 
@@ -17,7 +17,7 @@ pub enum VpcCmd {
 
 impl Default for VpcCmd {
     fn default() -> Self {
-        Self::List(ListVpcCmd::default())
+        Self::List(ListVpcCmd)
     }
 }
 
@@ -77,7 +77,7 @@ pub enum PeeringCmd {
 
 impl Default for PeeringCmd {
     fn default() -> Self {
-        Self::Create(CreatePeerCmd::default())
+        Self::Create(CreatePeerCmd)
     }
 }
 
@@ -10,11 +10,24 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
     let mut version = "0.0.0".to_string();
     let mut description = "".to_string(); // Added description
     let mut strict = false;
+    let mut response_files = false;
+    let mut infer_long_args = false;
+    let mut infer_subcommands = false;
+    let mut external_subcommands: Option<Option<syn::Path>> = None;
+    let mut repl = false;
+    let mut prompt_missing = false;
+    let mut confirm: Option<Option<String>> = None;
     let mut action_fn = None;
+    let mut config_file: Option<Option<String>> = None;
+    let mut dotenv: Option<String> = None;
+    let mut env_prefix: Option<String> = None;
 
     let mut flag_registrations = Vec::new();
+    let mut arg_registrations = Vec::new();
+    let mut group_registrations = Vec::new();
     let mut subcommand_registrations = Vec::new();
     let mut middleware_registrations = Vec::new();
+    let mut provider_registrations: Option<Vec<proc_macro2::TokenStream>> = None;
 
     // Automatic dispatch support
     let mut dispatch_field: Option<(syn::Ident, syn::Type)> = None;
@@ -55,6 +68,35 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                                 if let Expr::Path(expr_path) = nv.value {
                                     action_fn = Some(expr_path.path);
                                 }
+                            } else if nv.path.is_ident("external_subcommands") {
+                                // external_subcommands = path::to::fn
+                                if let Expr::Path(expr_path) = nv.value {
+                                    external_subcommands = Some(Some(expr_path.path));
+                                }
+                            } else if nv.path.is_ident("config_file") {
+                                if let Expr::Lit(expr_lit) = nv.value {
+                                    if let Lit::Str(lit) = expr_lit.lit {
+                                        config_file = Some(Some(lit.value()));
+                                    }
+                                }
+                            } else if nv.path.is_ident("confirm") {
+                                if let Expr::Lit(expr_lit) = nv.value {
+                                    if let Lit::Str(lit) = expr_lit.lit {
+                                        confirm = Some(Some(lit.value()));
+                                    }
+                                }
+                            } else if nv.path.is_ident("env_prefix") {
+                                if let Expr::Lit(expr_lit) = nv.value {
+                                    if let Lit::Str(lit) = expr_lit.lit {
+                                        env_prefix = Some(lit.value());
+                                    }
+                                }
+                            } else if nv.path.is_ident("dotenv") {
+                                if let Expr::Lit(expr_lit) = nv.value {
+                                    if let Lit::Str(lit) = expr_lit.lit {
+                                        dotenv = Some(lit.value());
+                                    }
+                                }
                             } else if nv.path.is_ident("strict") {
                                 if let Expr::Lit(expr_lit) = nv.value {
                                     if let Lit::Bool(lit) = expr_lit.lit {
@@ -78,6 +120,46 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                                         });
                                     }
                                 }
+                            } else if list.path.is_ident("args") {
+                                // args(Arg1, Arg2)
+                                let types = list
+                                    .parse_args_with(
+                                        syn::punctuated::Punctuated::<syn::Type, syn::Token![,]>::parse_terminated,
+                                    )
+                                    .ok();
+                                if let Some(types) = types {
+                                    for ty in types {
+                                        arg_registrations.push(quote! {
+                                            args.push(koral::internal::arg::ArgDef::from_trait::<#ty>());
+                                        });
+                                    }
+                                }
+                            } else if list.path.is_ident("groups") {
+                                // groups(Group1, Group2)
+                                let types = list
+                                    .parse_args_with(
+                                        syn::punctuated::Punctuated::<syn::Type, syn::Token![,]>::parse_terminated,
+                                    )
+                                    .ok();
+                                if let Some(types) = types {
+                                    for ty in types {
+                                        group_registrations.push(quote! {
+                                            groups.push(koral::internal::group::GroupDef::from_trait::<#ty>());
+                                        });
+                                    }
+                                }
+                            } else if list.path.is_ident("group") {
+                                // group(name = "target", flags(Flag1, Flag2), required, multiple = false)
+                                let nested = list
+                                    .parse_args_with(
+                                        syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                                    )
+                                    .expect("Failed to parse group");
+                                let group_def =
+                                    crate::derive_group::parse_group(nested).group_def();
+                                group_registrations.push(quote! {
+                                    groups.push(#group_def);
+                                });
                             } else if list.path.is_ident("middleware") {
                                 // middleware(MW1, MW2)
                                 let types = list
@@ -92,6 +174,20 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                                         });
                                     }
                                 }
+                            } else if list.path.is_ident("providers") {
+                                // providers(Provider1, Provider2)
+                                let types = list
+                                    .parse_args_with(
+                                        syn::punctuated::Punctuated::<syn::Type, syn::Token![,]>::parse_terminated,
+                                    )
+                                    .expect("Failed to parse providers list");
+                                let registrations =
+                                    provider_registrations.get_or_insert_with(Vec::new);
+                                for ty in types {
+                                    registrations.push(quote! {
+                                        providers.push(Box::new(<#ty as Default>::default()));
+                                    });
+                                }
                             } else if list.path.is_ident("subcommands") {
                                 // subcommands(Cmd1, Cmd2)
                                 let types = list
@@ -111,6 +207,24 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                         Meta::Path(path) => {
                             if path.is_ident("strict") {
                                 strict = true;
+                            } else if path.is_ident("config_file") {
+                                config_file = Some(None);
+                            } else if path.is_ident("dotenv") {
+                                dotenv = Some(".env".to_string());
+                            } else if path.is_ident("response_files") {
+                                response_files = true;
+                            } else if path.is_ident("infer_long_args") {
+                                infer_long_args = true;
+                            } else if path.is_ident("infer_subcommands") {
+                                infer_subcommands = true;
+                            } else if path.is_ident("external_subcommands") {
+                                external_subcommands = Some(None);
+                            } else if path.is_ident("repl") {
+                                repl = true;
+                            } else if path.is_ident("prompt_missing") {
+                                prompt_missing = true;
+                            } else if path.is_ident("confirm") {
+                                confirm = Some(None);
                             }
                         }
                     }
@@ -126,6 +240,7 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                 let ty = field.ty;
                 let mut is_subcommand = false;
                 let mut is_middleware = false;
+                let mut is_provider = false;
                 let mut ignore = false;
 
                 for attr in field.attrs {
@@ -140,6 +255,8 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                                         ignore = true;
                                     } else if path.is_ident("middleware") {
                                         is_middleware = true;
+                                    } else if path.is_ident("provider") {
+                                        is_provider = true;
                                     }
                                 }
                             }
@@ -160,6 +277,14 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                     });
                 }
 
+                if is_provider {
+                    provider_registrations
+                        .get_or_insert_with(Vec::new)
+                        .push(quote! {
+                            providers.push(Box::new(self.#ident.clone()));
+                        });
+                }
+
                 if is_subcommand {
                     // It's a subcommand provider
                     subcommand_registrations.push(quote! {
@@ -181,6 +306,54 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
         }
     }
 
+    // `#[app(repl)]` adds a `shell` subcommand starting a session
+    let repl_dispatch = if repl {
+        subcommand_registrations.push(quote! {
+            subs.push(koral::repl::shell_command());
+        });
+        quote! {
+            if ctx.args.first().map(String::as_str) == Some(koral::repl::SHELL_COMMAND) {
+                let terminal = ctx.terminal();
+                let (state, _) = ctx.into_dispatch();
+                return koral::repl::Repl::new(self).terminal(terminal).run_nested(state);
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Unrecognised subcommands go to the handler, or to `<app>-<name>` on PATH
+    let external_dispatch = match &external_subcommands {
+        Some(Some(handler)) => quote! {
+            if !ctx.args.is_empty() {
+                return koral::internal::handler::call_handler(#handler, self, ctx);
+            }
+        },
+        Some(None) => quote! {
+            if !ctx.args.is_empty() {
+                return koral::external::run(self, &ctx);
+            }
+        },
+        None => quote! {},
+    };
+    if external_subcommands.is_some() && !arg_registrations.is_empty() {
+        panic!("external_subcommands cannot be combined with args(...)");
+    }
+    let external_subcommands = external_subcommands.is_some();
+
+    // `#[app(confirm = "...")]` asks before the handler runs, `--yes` skips the question
+    if confirm.is_some() {
+        flag_registrations.push(quote! {
+            koral::confirm::add_yes_flag(&mut flags);
+        });
+    }
+    let confirm_check = match &confirm {
+        Some(Some(question)) => quote! {
+            koral::confirm::require(#question, &ctx)?;
+        },
+        _ => quote! {},
+    };
+
     // Inject automatic dispatch logic if a subcommand field exists
     let action_impl = if let Some((sub_ident, sub_ty)) = dispatch_field {
         let user_action = if let Some(action) = action_fn {
@@ -208,47 +381,105 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                         // ctx.args[0] IS the subcommand name.
 
                         let sub_args = ctx.args.clone();
-
-                         // We need to access state if available.
-                         // ctx.state is Option<&mut dyn Any>.
-                         // But we cannot move out of ctx.state if we need ctx later (which we don't if we return).
-                         // However, ctx is not Copy.
-                         // Because `run_with_state` takes `&mut dyn Any`, we can use `ctx.state`.
-                         // But `ctx` consumes itself in `execute`.
-                         // We can match on `ctx.state`.
-
-                         match ctx.state {
-                             Some(state) => {
-                                 return self.#sub_ident.run_with_state(state, sub_args);
-                             }
-                             None => {
-                                 return self.#sub_ident.run(sub_args);
-                             }
-                         }
+                        let (state, inherited) = ctx.into_dispatch();
+                        return koral::traits::App::run_nested(&mut self.#sub_ident, state, sub_args, inherited);
                      }
                  }
 
+                 #repl_dispatch
+                 #external_dispatch
+
                  // Fallback to user action
                  koral::internal::parser::validate_required_flags(&koral::traits::App::flags(self), &ctx.flags)?;
+                 koral::internal::parser::validate_required_groups(&koral::traits::App::groups(self), &koral::traits::App::flags(self), &ctx.flags)?;
+                 koral::internal::parser::validate_args(&koral::traits::App::args(self), &ctx.args, &ctx.arg_values)?;
+                 #confirm_check
                  #user_action
             }
         }
     } else if let Some(action) = action_fn {
         quote! {
             fn execute(&mut self, ctx: koral::Context) -> koral::KoralResult<()> {
+                #repl_dispatch
+                #external_dispatch
                 koral::internal::parser::validate_required_flags(&koral::traits::App::flags(self), &ctx.flags)?;
+                koral::internal::parser::validate_required_groups(&koral::traits::App::groups(self), &koral::traits::App::flags(self), &ctx.flags)?;
+                koral::internal::parser::validate_args(&koral::traits::App::args(self), &ctx.args, &ctx.arg_values)?;
+                #confirm_check
                 koral::internal::handler::call_handler(#action, self, ctx)
             }
         }
     } else {
         quote! {
             fn execute(&mut self, ctx: koral::Context) -> koral::KoralResult<()> {
+                #repl_dispatch
+                #external_dispatch
                 koral::internal::parser::validate_required_flags(&koral::traits::App::flags(self), &ctx.flags)?;
+                koral::internal::parser::validate_required_groups(&koral::traits::App::groups(self), &koral::traits::App::flags(self), &ctx.flags)?;
+                koral::internal::parser::validate_args(&koral::traits::App::args(self), &ctx.args, &ctx.arg_values)?;
+                #confirm_check
                 Ok(())
             }
         }
     };
 
+    // `#[app(prompt_missing)]` lets `--no-input` turn the prompts off
+    if prompt_missing {
+        flag_registrations.push(quote! {
+            if !flags.iter().any(|f| f.name == koral::prompt::NO_INPUT_FLAG) {
+                flags.push(koral::prompt::no_input_flag());
+            }
+        });
+    }
+
+    // `#[app(config_file)]` names the file after the app
+    let config_impl = match config_file {
+        Some(file) => {
+            let config_name = file.unwrap_or_else(|| app_name.clone());
+            flag_registrations.push(quote! {
+                if !flags.iter().any(|f| f.name == "config") {
+                    flags.push(koral::provider::FileProvider::config_flag());
+                }
+            });
+            quote! {
+                fn config_name(&self) -> Option<&str> {
+                    Some(#config_name)
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    // Without `providers(...)` the parent's providers, or the environment, apply
+    let providers_impl = match provider_registrations {
+        Some(registrations) => quote! {
+            fn providers(&self) -> Option<Vec<Box<dyn koral::provider::ValueProvider>>> {
+                let mut providers: Vec<Box<dyn koral::provider::ValueProvider>> = Vec::new();
+                #(#registrations)*
+                Some(providers)
+            }
+        },
+        None => quote! {},
+    };
+
+    let dotenv_impl = match dotenv {
+        Some(file) => quote! {
+            fn dotenv_name(&self) -> Option<&str> {
+                Some(#file)
+            }
+        },
+        None => quote! {},
+    };
+
+    let env_prefix_impl = match env_prefix {
+        Some(prefix) => quote! {
+            fn env_prefix(&self) -> Option<&str> {
+                Some(#prefix)
+            }
+        },
+        None => quote! {},
+    };
+
     let expanded = quote! {
         impl koral::traits::App for #name {
             fn name(&self) -> &str {
@@ -264,11 +495,23 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
             }
 
             fn flags(&self) -> Vec<koral::internal::flag::FlagDef> {
-                let mut flags = Vec::new();
+                let mut flags: Vec<koral::internal::flag::FlagDef> = Vec::new();
                 #(#flag_registrations)*
                 flags
             }
 
+            fn args(&self) -> Vec<koral::internal::arg::ArgDef> {
+                let mut args = Vec::new();
+                #(#arg_registrations)*
+                args
+            }
+
+            fn groups(&self) -> Vec<koral::internal::group::GroupDef> {
+                let mut groups = Vec::new();
+                #(#group_registrations)*
+                groups
+            }
+
             fn subcommands(&self) -> Vec<koral::internal::command::CommandDef> {
                 let mut subs = Vec::new();
                 #(#subcommand_registrations)*
@@ -279,12 +522,40 @@ pub fn impl_derive_app(input: TokenStream) -> TokenStream {
                 #strict
             }
 
+            fn response_files(&self) -> bool {
+                #response_files
+            }
+
+            fn infer_long_args(&self) -> bool {
+                #infer_long_args
+            }
+
+            fn infer_subcommands(&self) -> bool {
+                #infer_subcommands
+            }
+
+            fn external_subcommands(&self) -> bool {
+                #external_subcommands
+            }
+
+            fn prompt_missing(&self) -> bool {
+                #prompt_missing
+            }
+
             fn middlewares(&self) -> Vec<Box<dyn koral::Middleware>> {
                 let mut mws: Vec<Box<dyn koral::Middleware>> = Vec::new();
                 #(#middleware_registrations)*
                 mws
             }
 
+            #providers_impl
+
+            #config_impl
+
+            #dotenv_impl
+
+            #env_prefix_impl
+
             #action_impl
         }
 
@@ -0,0 +1,158 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Lit, Meta};
+
+use crate::derive_flag::vec_elem_type;
+
+pub fn impl_derive_arg(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+
+    // Default values
+    let lower = name.to_string().to_lowercase();
+    let mut arg_name = lower.strip_suffix("arg").unwrap_or(&lower).to_string();
+    let mut help = String::new();
+    let mut index: Option<usize> = None;
+    let mut arity: Option<String> = None;
+    let mut value_name: Option<String> = None;
+    let mut required: Option<bool> = None;
+
+    // Parse attributes
+    for attr in input.attrs {
+        if attr.path().is_ident("arg") {
+            let nested = attr
+                .parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                )
+                .unwrap();
+            for meta in nested {
+                if let Meta::NameValue(nv) = meta {
+                    if nv.path.is_ident("name") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                arg_name = lit.value();
+                            }
+                        }
+                    } else if nv.path.is_ident("help") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                help = lit.value();
+                            }
+                        }
+                    } else if nv.path.is_ident("index") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Int(lit) = expr_lit.lit {
+                                index = lit.base10_parse().ok();
+                            }
+                        }
+                    } else if nv.path.is_ident("arity") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                arity = Some(lit.value());
+                            }
+                        }
+                    } else if nv.path.is_ident("value_name") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                value_name = Some(lit.value());
+                            }
+                        }
+                    } else if nv.path.is_ident("required") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Bool(lit) = expr_lit.lit {
+                                required = Some(lit.value);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Determine value type
+    let (value_type, elem_type) = match input.data {
+        Data::Struct(data_struct) => match data_struct.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed.first().unwrap().ty;
+                (quote! { #ty }, vec_elem_type(ty))
+            }
+            _ => panic!("Arg derive only supports tuple structs with 1 element"),
+        },
+        _ => panic!("Arg derive only supports structs"),
+    };
+
+    // A `Vec<T>` value takes every remaining positional by default
+    let arity_quote = match arity.as_deref() {
+        Some("one") => quote! { koral::Arity::One },
+        Some("optional") => quote! { koral::Arity::Optional },
+        Some("many") => quote! { koral::Arity::Many },
+        Some(other) => panic!(
+            "Unknown arity '{}', expected \"one\", \"optional\" or \"many\"",
+            other
+        ),
+        None if elem_type.is_some() => quote! { koral::Arity::Many },
+        None => quote! { koral::Arity::One },
+    };
+
+    let index_quote = match index {
+        Some(i) => quote! { Some(#i) },
+        None => quote! { None },
+    };
+
+    let value_name_quote = match value_name {
+        Some(v) => quote! { Some(#v) },
+        None => quote! { None },
+    };
+
+    let required_impl = match required {
+        Some(r) => quote! {
+            fn required() -> bool {
+                #r
+            }
+        },
+        None => quote! {},
+    };
+
+    let from_values_impl = match elem_type {
+        Some(elem) => quote! {
+            <#elem as koral::traits::FlagValue>::from_each(values)
+        },
+        None => quote! {
+            <#value_type as koral::traits::FlagValue>::from_last(values)
+        },
+    };
+
+    let expanded = quote! {
+        impl koral::Arg for #name {
+            type Value = #value_type;
+
+            fn name() -> &'static str {
+                #arg_name
+            }
+
+            fn help() -> &'static str {
+                #help
+            }
+
+            fn index() -> Option<usize> {
+                #index_quote
+            }
+
+            fn arity() -> koral::Arity {
+                #arity_quote
+            }
+
+            fn value_name() -> Option<&'static str> {
+                #value_name_quote
+            }
+
+            #required_impl
+
+            fn from_values(values: &[&str]) -> Result<Self::Value, String> {
+                #from_values_impl
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -17,6 +17,19 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
     let mut required = false;
     let mut value_name: Option<String> = None;
     let mut help_heading: Option<String> = None;
+    let mut value_delimiter: Option<char> = None;
+    let mut count = false;
+    let mut negatable = false;
+    let mut from_file = false;
+    let mut sensitive = false;
+    let mut global = false;
+    let mut missing_value: Option<String> = None;
+    let mut conflicts_with: Vec<String> = Vec::new();
+    let mut requires: Vec<String> = Vec::new();
+    let mut required_if_eq: Vec<(String, String)> = Vec::new();
+    let mut required_unless_present: Vec<String> = Vec::new();
+    let mut default_value_if: Vec<(String, String, String)> = Vec::new();
+    let mut possible_values: Option<Vec<String>> = None;
 
     // Parse attributes
     for attr in input.attrs {
@@ -27,7 +40,48 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
                 )
                 .unwrap();
             for meta in nested {
-                if let Meta::NameValue(nv) = meta {
+                if let Meta::Path(path) = &meta {
+                    if path.is_ident("count") {
+                        count = true;
+                    } else if path.is_ident("negatable") {
+                        negatable = true;
+                    } else if path.is_ident("from_file") {
+                        from_file = true;
+                    } else if path.is_ident("sensitive") {
+                        sensitive = true;
+                    } else if path.is_ident("global") {
+                        global = true;
+                    }
+                } else if let Meta::List(list) = &meta {
+                    // Conditions: required_if_eq("mode", "remote")
+                    let strings: Vec<String> = list
+                        .parse_args_with(
+                            syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated,
+                        )
+                        .expect("Expected string literals")
+                        .iter()
+                        .map(|lit| lit.value())
+                        .collect();
+                    if list.path.is_ident("required_if_eq") {
+                        match strings.as_slice() {
+                            [flag, value] => required_if_eq.push((flag.clone(), value.clone())),
+                            _ => panic!("required_if_eq expects (\"flag\", \"value\")"),
+                        }
+                    } else if list.path.is_ident("required_unless_present") {
+                        required_unless_present.extend(strings);
+                    } else if list.path.is_ident("default_value_if") {
+                        match strings.as_slice() {
+                            [flag, value, default] => default_value_if.push((
+                                flag.clone(),
+                                value.clone(),
+                                default.clone(),
+                            )),
+                            _ => panic!(
+                                "default_value_if expects (\"flag\", \"value\", \"default\")"
+                            ),
+                        }
+                    }
+                } else if let Meta::NameValue(nv) = meta {
                     if nv.path.is_ident("name") {
                         if let Expr::Lit(expr_lit) = nv.value {
                             if let Lit::Str(lit) = expr_lit.lit {
@@ -72,6 +126,37 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
                                     .collect();
                             }
                         }
+                    } else if nv.path.is_ident("possible_values") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                possible_values = Some(
+                                    lit.value()
+                                        .split(',')
+                                        .map(|s| s.trim().to_string())
+                                        .collect(),
+                                );
+                            }
+                        }
+                    } else if nv.path.is_ident("conflicts_with") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                conflicts_with = lit
+                                    .value()
+                                    .split(',')
+                                    .map(|s| s.trim().to_string())
+                                    .collect();
+                            }
+                        }
+                    } else if nv.path.is_ident("requires") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                requires = lit
+                                    .value()
+                                    .split(',')
+                                    .map(|s| s.trim().to_string())
+                                    .collect();
+                            }
+                        }
                     } else if nv.path.is_ident("required") {
                         if let Expr::Lit(expr_lit) = nv.value {
                             if let Lit::Bool(lit) = expr_lit.lit {
@@ -90,6 +175,18 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
                                 help_heading = Some(lit.value());
                             }
                         }
+                    } else if nv.path.is_ident("missing_value") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Str(lit) = expr_lit.lit {
+                                missing_value = Some(lit.value());
+                            }
+                        }
+                    } else if nv.path.is_ident("value_delimiter") {
+                        if let Expr::Lit(expr_lit) = nv.value {
+                            if let Lit::Char(lit) = expr_lit.lit {
+                                value_delimiter = Some(lit.value());
+                            }
+                        }
                     }
                 }
             }
@@ -97,9 +194,9 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
     }
 
     // Determine value type and takes_value
-    let (value_type, takes_value, _is_bool) = match input.data {
+    let (value_type, takes_value, is_bool, elem_type) = match input.data {
         Data::Struct(data_struct) => match data_struct.fields {
-            Fields::Unit => (quote! { bool }, false, true),
+            Fields::Unit => (quote! { bool }, false, true, None),
             Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
                 let ty = &fields.unnamed.first().unwrap().ty;
                 let is_bool = if let syn::Type::Path(p) = ty {
@@ -107,17 +204,33 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
                 } else {
                     false
                 };
-                (quote! { #ty }, !is_bool, is_bool)
+                (quote! { #ty }, !is_bool, is_bool, vec_elem_type(ty))
             }
             _ => panic!("Flag derive only supports unit structs or tuple structs with 1 element"),
         },
         _ => panic!("Flag derive only supports structs"),
     };
 
-    if default_val.is_none() && _is_bool {
+    if negatable && !is_bool {
+        panic!("negatable is only supported on bool flags");
+    }
+
+    if default_val.is_none() && is_bool {
         default_val = Some("false".to_string());
     }
 
+    // A counting flag takes no value; its value is the number of occurrences
+    let takes_value = takes_value && !count;
+    if default_val.is_none() && count {
+        default_val = Some("0".to_string());
+    }
+
+    // A `Vec<T>` value collects every occurrence of the flag
+    let multiple = elem_type.is_some();
+    if value_delimiter.is_some() && !multiple {
+        panic!("value_delimiter is only supported on Vec flags");
+    }
+
     let short_quote = match short {
         Some(c) => quote! { Some(#c) },
         None => quote! { None },
@@ -143,15 +256,62 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
         None => quote! { None },
     };
 
-    let default_impl = if let Some(d) = default_val {
-        quote! {
-            fn default_value() -> Option<Self::Value> {
-                // We rely on FromStr for the value type
-                 <#value_type as std::str::FromStr>::from_str(#d).ok()
+    let default_quote = match default_val {
+        Some(d) => quote! { Some(#d) },
+        None => quote! { None },
+    };
+
+    if missing_value.is_some() && !takes_value {
+        panic!("missing_value is only supported on flags taking a value");
+    }
+
+    let missing_value_quote = match missing_value {
+        Some(v) => quote! { Some(#v) },
+        None => quote! { None },
+    };
+
+    let (required_if_eq_flags, required_if_eq_values): (Vec<_>, Vec<_>) =
+        required_if_eq.into_iter().unzip();
+    let mut default_if_flags = Vec::new();
+    let mut default_if_values = Vec::new();
+    let mut default_if_defaults = Vec::new();
+    for (flag, value, default) in default_value_if {
+        default_if_flags.push(flag);
+        default_if_values.push(value);
+        default_if_defaults.push(default);
+    }
+
+    let value_delimiter_quote = match value_delimiter {
+        Some(c) => quote! { Some(#c) },
+        None => quote! { None },
+    };
+
+    // Enums deriving FlagValue know their values; other types accept anything
+    let possible_values_quote = match possible_values {
+        Some(values) => quote! { vec![#(#values),*] },
+        None => {
+            let probed = elem_type
+                .as_ref()
+                .map(|e| quote! { #e })
+                .unwrap_or(value_type.clone());
+            quote! {
+                #[allow(unused_imports)]
+                use koral::traits::{ViaAnyValue, ViaPossibleValues};
+                (&koral::traits::PossibleValuesProbe::<#probed>::new()).possible_values()
             }
         }
-    } else {
-        quote! {} // Default is None
+    };
+
+    let from_values_impl = match elem_type {
+        Some(elem) => quote! {
+            <#elem as koral::traits::FlagValue>::from_each(values)
+        },
+        None if is_bool => quote! {
+            <#value_type as koral::traits::FlagValue>::from_switch(values)
+        },
+        None => quote! {
+            <#value_type as koral::traits::FlagValue>::from_last(values)
+        },
     };
 
     let expanded = quote! {
@@ -174,7 +334,13 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
                 #takes_value
             }
 
-            #default_impl
+            fn from_values(values: &[&str]) -> Result<Self::Value, String> {
+                #from_values_impl
+            }
+
+            fn default_str() -> Option<&'static str> {
+                #default_quote
+            }
 
             fn env() -> Option<&'static str> {
                 #env_quote
@@ -199,8 +365,82 @@ pub fn impl_derive_flag(input: TokenStream) -> TokenStream {
             fn help_heading() -> Option<&'static str> {
                 #help_heading_quote
             }
+
+            fn multiple() -> bool {
+                #multiple
+            }
+
+            fn value_delimiter() -> Option<char> {
+                #value_delimiter_quote
+            }
+
+            fn count() -> bool {
+                #count
+            }
+
+            fn negatable() -> bool {
+                #negatable
+            }
+
+            fn from_file() -> bool {
+                #from_file
+            }
+
+            fn sensitive() -> bool {
+                #sensitive
+            }
+
+            fn global() -> bool {
+                #global
+            }
+
+            fn missing_value() -> Option<&'static str> {
+                #missing_value_quote
+            }
+
+            fn conflicts_with() -> Vec<&'static str> {
+                vec![#(#conflicts_with),*]
+            }
+
+            fn requires() -> Vec<&'static str> {
+                vec![#(#requires),*]
+            }
+
+            fn required_if_eq() -> Vec<(&'static str, &'static str)> {
+                vec![#((#required_if_eq_flags, #required_if_eq_values)),*]
+            }
+
+            fn required_unless_present() -> Vec<&'static str> {
+                vec![#(#required_unless_present),*]
+            }
+
+            fn default_value_if() -> Vec<(&'static str, &'static str, &'static str)> {
+                vec![#((#default_if_flags, #default_if_values, #default_if_defaults)),*]
+            }
+
+            fn possible_values() -> Vec<&'static str> {
+                #possible_values_quote
+            }
         }
     };
 
     TokenStream::from(expanded)
 }
+
+/// Returns `T` when the given type is `Vec<T>`.
+pub(crate) fn vec_elem_type(ty: &syn::Type) -> Option<syn::Type> {
+    let syn::Type::Path(p) = ty else {
+        return None;
+    };
+    let segment = p.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(elem) => Some(elem.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
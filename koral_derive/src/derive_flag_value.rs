@@ -17,6 +17,8 @@ pub fn impl_derive_flag_value(input: TokenStream) -> TokenStream {
                 }
             });
 
+            let possible_values = variants.iter().map(|v| v.ident.to_string().to_lowercase());
+
             let to_string_arms = variants.iter().map(|v| {
                 let ident = &v.ident;
                 let ident_str = ident.to_string().to_lowercase();
@@ -44,6 +46,12 @@ pub fn impl_derive_flag_value(input: TokenStream) -> TokenStream {
                         }
                     }
                 }
+
+                impl koral::traits::PossibleValues for #name {
+                    fn possible_values() -> Vec<&'static str> {
+                        vec![#(#possible_values),*]
+                    }
+                }
             };
             gen.into()
         }
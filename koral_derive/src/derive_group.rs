@@ -0,0 +1,139 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, DeriveInput, Expr, Lit, Meta, Token};
+
+/// Settings shared by `#[derive(FlagGroup)]` and `#[app(group(...))]`.
+pub(crate) struct GroupSpec {
+    pub name: Option<String>,
+    pub flags: Vec<syn::Type>,
+    pub required: bool,
+    pub multiple: bool,
+}
+
+/// Parse `name = "...", flags(A, B), required, multiple = false`.
+pub(crate) fn parse_group(nested: Punctuated<Meta, Token![,]>) -> GroupSpec {
+    let mut spec = GroupSpec {
+        name: None,
+        flags: Vec::new(),
+        required: false,
+        multiple: true,
+    };
+
+    for meta in nested {
+        match meta {
+            Meta::Path(path) => {
+                if path.is_ident("required") {
+                    spec.required = true;
+                } else if path.is_ident("multiple") {
+                    spec.multiple = true;
+                }
+            }
+            Meta::NameValue(nv) => {
+                if nv.path.is_ident("name") {
+                    if let Expr::Lit(expr_lit) = nv.value {
+                        if let Lit::Str(lit) = expr_lit.lit {
+                            spec.name = Some(lit.value());
+                        }
+                    }
+                } else if nv.path.is_ident("required") {
+                    if let Expr::Lit(expr_lit) = nv.value {
+                        if let Lit::Bool(lit) = expr_lit.lit {
+                            spec.required = lit.value;
+                        }
+                    }
+                } else if nv.path.is_ident("multiple") {
+                    if let Expr::Lit(expr_lit) = nv.value {
+                        if let Lit::Bool(lit) = expr_lit.lit {
+                            spec.multiple = lit.value;
+                        }
+                    }
+                }
+            }
+            Meta::List(list) => {
+                if list.path.is_ident("flags") {
+                    // flags(Flag1, Flag2)
+                    let types = list
+                        .parse_args_with(Punctuated::<syn::Type, Token![,]>::parse_terminated)
+                        .expect("Failed to parse group flags list");
+                    spec.flags.extend(types);
+                }
+            }
+        }
+    }
+    spec
+}
+
+impl GroupSpec {
+    /// Expression building a `GroupDef` for this group.
+    pub(crate) fn group_def(&self) -> proc_macro2::TokenStream {
+        let name = self
+            .name
+            .as_deref()
+            .expect("group(...) requires a name = \"...\"");
+        let flags = &self.flags;
+        let required = self.required;
+        let multiple = self.multiple;
+        quote! {
+            koral::internal::group::GroupDef {
+                name: #name.to_string(),
+                flags: vec![#(<#flags as koral::Flag>::name().to_string()),*],
+                required: #required,
+                multiple: #multiple,
+            }
+        }
+    }
+}
+
+pub fn impl_derive_flag_group(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+
+    // Default values
+    let lower = name.to_string().to_lowercase();
+    let mut spec = GroupSpec {
+        name: None,
+        flags: Vec::new(),
+        required: false,
+        multiple: true,
+    };
+
+    // Parse attributes
+    for attr in input.attrs {
+        if attr.path().is_ident("group") {
+            let nested = attr
+                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .unwrap();
+            spec = parse_group(nested);
+        }
+    }
+
+    let group_name = spec
+        .name
+        .unwrap_or_else(|| lower.strip_suffix("group").unwrap_or(&lower).to_string());
+    let flags = &spec.flags;
+    let required = spec.required;
+    let multiple = spec.multiple;
+
+    let expanded = quote! {
+        impl koral::FlagGroup for #name {
+            fn name() -> &'static str {
+                #group_name
+            }
+
+            fn flags() -> Vec<&'static str> {
+                vec![#(<#flags as koral::Flag>::name()),*]
+            }
+
+            fn required() -> bool {
+                #required
+            }
+
+            fn multiple() -> bool {
+                #multiple
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -16,7 +16,6 @@ pub fn impl_derive_subcommand(input: TokenStream) -> TokenStream {
 
     // For App implementation
     let mut run_arms = Vec::new();
-    let mut run_state_arms = Vec::new();
     let mut execute_arms = Vec::new();
     let mut name_arms = Vec::new();
     let mut flag_arms = Vec::new(); // Usually empty or delegated?
@@ -85,14 +84,11 @@ pub fn impl_derive_subcommand(input: TokenStream) -> TokenStream {
                     Self::#variant_name(cmd) => {
                         let mut passed_args = vec![#cmd_name.to_string()];
                         passed_args.extend(next_args);
-                        cmd.run(passed_args)
-                    },
-                });
-                run_state_arms.push(quote! {
-                    Self::#variant_name(cmd) => {
-                        let mut passed_args = vec![#cmd_name.to_string()];
-                        passed_args.extend(next_args);
-                        cmd.run_with_state(state, passed_args)
+                        inherited.path.push(#cmd_name.to_string());
+                        inherited.env_prefix = inherited
+                            .env_prefix
+                            .map(|p| koral::internal::flag::scoped_env_prefix(&p, #cmd_name));
+                        cmd.run_nested(state, passed_args, inherited)
                     },
                 });
                 execute_arms.push(quote! {
@@ -112,7 +108,8 @@ pub fn impl_derive_subcommand(input: TokenStream) -> TokenStream {
                    koral::internal::command::CommandDef::new(#cmd_name, "")
                        .with_aliases(vec![#(#aliases.to_string()),*])
                        .with_subcommands(<#inner_ty as koral::traits::FromArgs>::get_subcommands())
-                       .with_flags(<#inner_ty as koral::traits::App>::flags(&<#inner_ty as Default>::default())),
+                       .with_flags(<#inner_ty as koral::traits::App>::flags(&<#inner_ty as Default>::default()))
+                       .with_args(<#inner_ty as koral::traits::App>::args(&<#inner_ty as Default>::default())),
                });
             }
             _ => panic!("Subcommand variants must be Unit or Tuple with 1 element"),
@@ -188,7 +185,12 @@ pub fn impl_derive_subcommand(input: TokenStream) -> TokenStream {
                 }
             }
 
-            fn run(&mut self, args: Vec<String>) -> koral::KoralResult<()> {
+            fn run_nested(
+                &mut self,
+                state: Option<&mut dyn std::any::Any>,
+                args: Vec<String>,
+                mut inherited: koral::traits::Inherited,
+            ) -> koral::KoralResult<()> {
                 // Check if help is requested for THIS command (the Group)
                 // args logic similar to App trait default run
                 let help_invoked = args.iter().position(|a| a == "--help" || a == "-h");
@@ -211,8 +213,7 @@ pub fn impl_derive_subcommand(input: TokenStream) -> TokenStream {
                     };
 
                     if should_print_help {
-                        self.print_help();
-                        return Ok(());
+                        return self.write_help(&inherited.terminal());
                     }
                 }
 
@@ -226,40 +227,6 @@ pub fn impl_derive_subcommand(input: TokenStream) -> TokenStream {
                 }
             }
 
-            fn run_with_state(&mut self, state: &mut dyn std::any::Any, args: Vec<String>) -> koral::KoralResult<()> {
-                // Same help logic as run
-                let help_invoked = args.iter().position(|a| a == "--help" || a == "-h");
-                if let Some(h_idx) = help_invoked {
-                    let subcommands = self.subcommands();
-                     let sub_idx = args.iter().enumerate().find_map(|(i, arg)| {
-                        if subcommands.iter().any(|s| s.name == *arg || s.aliases.contains(arg)) {
-                            Some(i)
-                        } else {
-                            None
-                        }
-                    });
-
-                    let should_print_help = match sub_idx {
-                        Some(s_idx) => h_idx < s_idx,
-                        None => true,
-                    };
-
-                    if should_print_help {
-                        self.print_help();
-                        return Ok(());
-                    }
-                }
-
-                let next_args = if !args.is_empty() {
-                    args[1..].to_vec()
-                } else {
-                    vec![]
-                };
-                match self {
-                    #(#run_state_arms)*
-                }
-            }
-
             fn subcommands(&self) -> Vec<koral::internal::command::CommandDef> {
                <Self as koral::traits::FromArgs>::get_subcommands()
             }
@@ -1,8 +1,10 @@
 use proc_macro::TokenStream;
 
 mod derive_app;
+mod derive_arg;
 mod derive_flag;
 mod derive_flag_value;
+mod derive_group;
 
 mod derive_subcommand;
 
@@ -11,6 +13,16 @@ pub fn derive_flag(input: TokenStream) -> TokenStream {
     derive_flag::impl_derive_flag(input)
 }
 
+#[proc_macro_derive(Arg, attributes(arg))]
+pub fn derive_arg(input: TokenStream) -> TokenStream {
+    derive_arg::impl_derive_arg(input)
+}
+
+#[proc_macro_derive(FlagGroup, attributes(group))]
+pub fn derive_flag_group(input: TokenStream) -> TokenStream {
+    derive_group::impl_derive_flag_group(input)
+}
+
 #[proc_macro_derive(Subcommand, attributes(subcommand))]
 pub fn derive_subcommand(input: TokenStream) -> TokenStream {
     derive_subcommand::impl_derive_subcommand(input)
@@ -0,0 +1,139 @@
+use crate::command::CommandDef;
+use crate::error::{KoralError, KoralResult};
+use crate::flag::FlagDef;
+
+/// A command alias defined by the user in the configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UserAlias {
+    /// Name typed in place of a command
+    pub name: String,
+    /// Arguments the name stands for
+    pub expansion: Vec<String>,
+}
+
+/// Load the aliases of the `[alias]` table.
+///
+/// Aliases named like a built-in command are dropped, so they can never
+/// shadow it.
+pub(crate) fn load(
+    config: &crate::provider::FileProvider,
+    subcommands: &[CommandDef],
+) -> KoralResult<Vec<UserAlias>> {
+    Ok(config
+        .aliases()?
+        .into_iter()
+        .filter(|(name, _)| {
+            !subcommands
+                .iter()
+                .filter(|s| !s.external)
+                .any(|s| &s.name == name || s.aliases.contains(name))
+        })
+        .map(|(name, expansion)| UserAlias { name, expansion })
+        .collect())
+}
+
+/// Replace an alias given as the command with its expansion.
+///
+/// `args` excludes the program name. Aliases may expand to other aliases;
+/// an alias reached twice is an error.
+pub(crate) fn expand(
+    mut args: Vec<String>,
+    aliases: &[UserAlias],
+    flags: &[FlagDef],
+) -> KoralResult<Vec<String>> {
+    let mut seen: Vec<&str> = Vec::new();
+    while let Some(idx) = command_index(&args, flags) {
+        let Some(alias) = aliases.iter().find(|a| a.name == args[idx]) else {
+            break;
+        };
+        if seen.contains(&alias.name.as_str()) {
+            return Err(KoralError::Validation(format!(
+                "Alias '{}' expands to itself ({} -> {})",
+                alias.name,
+                seen.join(" -> "),
+                alias.name
+            )));
+        }
+        seen.push(&alias.name);
+        args.splice(idx..=idx, alias.expansion.iter().cloned());
+    }
+    Ok(args)
+}
+
+/// Position of the first argument that is neither a flag nor a flag's value.
+fn command_index(args: &[String], flags: &[FlagDef]) -> Option<usize> {
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            return None;
+        }
+        let takes_next = if let Some(long) = arg.strip_prefix("--") {
+            !long.contains('=')
+                && flags
+                    .iter()
+                    .find(|f| {
+                        f.long.as_deref().unwrap_or(&f.name) == long
+                            || f.aliases.iter().any(|a| a == long)
+                    })
+                    .is_some_and(|f| f.takes_value && f.missing_value.is_none())
+        } else if let Some(shorts) = arg.strip_prefix('-').filter(|s| !s.is_empty()) {
+            // A flag taking a value uses the rest of the cluster, or else the next argument
+            let mut chars = shorts.chars();
+            let mut takes_next = false;
+            while let Some(c) = chars.next() {
+                if let Some(flag) = flags.iter().find(|f| f.short == Some(c) && f.takes_value) {
+                    takes_next = chars.as_str().is_empty() && flag.missing_value.is_none();
+                    break;
+                }
+            }
+            takes_next
+        } else {
+            return Some(i);
+        };
+        i += if takes_next { 2 } else { 1 };
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(name: &str, expansion: &[&str]) -> UserAlias {
+        UserAlias {
+            name: name.to_string(),
+            expansion: expansion.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_expand_nested_aliases() {
+        let aliases = [
+            alias("lsc", &["k8s", "clusters", "list"]),
+            alias("lscj", &["lsc", "--output", "json"]),
+        ];
+        let args = expand(strings(&["-v", "lscj", "extra"]), &aliases, &[]).unwrap();
+        assert_eq!(
+            args,
+            strings(&["-v", "k8s", "clusters", "list", "--output", "json", "extra"])
+        );
+    }
+
+    #[test]
+    fn test_expand_rejects_recursion() {
+        let aliases = [alias("a", &["b"]), alias("b", &["--x", "a"])];
+        assert!(expand(strings(&["a"]), &aliases, &[]).is_err());
+    }
+
+    #[test]
+    fn test_expand_ignores_arguments_after_double_dash() {
+        let aliases = [alias("lsc", &["k8s"])];
+        let args = expand(strings(&["--", "lsc"]), &aliases, &[]).unwrap();
+        assert_eq!(args, strings(&["--", "lsc"]));
+    }
+}
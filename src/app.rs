@@ -1,7 +1,11 @@
+use crate::arg::{Arg, ArgDef};
 use crate::context::Context;
 use crate::error::KoralResult;
 use crate::flag::{Flag, FlagDef};
+use crate::group::{FlagGroup, GroupDef};
+use crate::provider::ValueProvider;
 use crate::traits::App as AppTrait;
+use std::sync::Arc;
 
 /// The builder struct for defining an application.
 /// Type alias for the application action handler
@@ -13,9 +17,21 @@ pub struct App {
     version: String,
     description: String,
     flags: Vec<FlagDef>,
+    args: Vec<ArgDef>,
+    groups: Vec<GroupDef>,
     subcommands: Vec<Box<dyn AppTrait>>,
     action: Option<ActionFn>,
     strict: bool,
+    config_name: Option<String>,
+    dotenv_name: Option<String>,
+    env_prefix: Option<String>,
+    response_files: bool,
+    infer_long_args: bool,
+    infer_subcommands: bool,
+    external_subcommands: bool,
+    prompt_missing: bool,
+    confirm: Option<String>,
+    providers: Option<Vec<Arc<dyn ValueProvider>>>,
 }
 
 impl App {
@@ -26,9 +42,21 @@ impl App {
             version: "0.0.0".to_string(),
             description: String::new(),
             flags: Vec::new(),
+            args: Vec::new(),
+            groups: Vec::new(),
             subcommands: Vec::new(),
             action: None,
             strict: false,
+            config_name: None,
+            dotenv_name: None,
+            env_prefix: None,
+            response_files: false,
+            infer_long_args: false,
+            infer_subcommands: false,
+            external_subcommands: false,
+            prompt_missing: false,
+            confirm: None,
+            providers: None,
         }
     }
 
@@ -50,12 +78,101 @@ impl App {
         self
     }
 
+    /// Read flag values from a configuration file named after `name`.
+    ///
+    /// See [`FileProvider::discover`](crate::provider::FileProvider::discover)
+    /// for how the file is located.
+    pub fn config_file(mut self, name: impl Into<String>) -> Self {
+        self.config_name = Some(name.into());
+        self
+    }
+
+    /// Fall back to a dotenv file named `name` for `env` lookups.
+    ///
+    /// See [`DotenvProvider::discover`](crate::provider::DotenvProvider::discover)
+    /// for how the file is located.
+    pub fn dotenv(mut self, name: impl Into<String>) -> Self {
+        self.dotenv_name = Some(name.into());
+        self
+    }
+
+    /// Read every flag without an explicit `env` from `<PREFIX>_<FLAG_NAME>`.
+    pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Expand `@file` arguments into the arguments listed in that file.
+    pub fn response_files(mut self, enabled: bool) -> Self {
+        self.response_files = enabled;
+        self
+    }
+
+    /// Accept unambiguous prefixes of long flags (`--verb` for `--verbose`).
+    pub fn infer_long_args(mut self, infer: bool) -> Self {
+        self.infer_long_args = infer;
+        self
+    }
+
+    /// Accept unambiguous prefixes of subcommand names (`clu` for `clusters`).
+    pub fn infer_subcommands(mut self, infer: bool) -> Self {
+        self.infer_subcommands = infer;
+        self
+    }
+
+    /// Run unrecognised subcommands `foo` as the executable `<name>-foo` from `PATH`.
+    ///
+    /// Has no effect on apps with positional arguments, whose values are not subcommands.
+    pub fn external_subcommands(mut self, enabled: bool) -> Self {
+        self.external_subcommands = enabled;
+        self
+    }
+
+    /// Ask for missing required flags when run from a terminal.
+    ///
+    /// Adds the `--no-input` flag turning prompts off.
+    pub fn prompt_missing(mut self, enabled: bool) -> Self {
+        self.prompt_missing = enabled;
+        self
+    }
+
+    /// Ask `question` before the action runs, unless `--yes` is given.
+    ///
+    /// `{name}` in the question is replaced by the value of the flag or argument `name`.
+    pub fn confirm(mut self, question: impl Into<String>) -> Self {
+        self.confirm = Some(question.into());
+        self
+    }
+
+    /// Add a source for flags missing from the command line.
+    ///
+    /// Providers are consulted in registration order. Registering any provider
+    /// replaces the default environment lookup; add `EnvProvider` to keep it.
+    pub fn provider<P: ValueProvider + 'static>(mut self, provider: P) -> Self {
+        self.providers
+            .get_or_insert_with(Vec::new)
+            .push(Arc::new(provider));
+        self
+    }
+
     /// Register a type-based flag.
     pub fn register<F: Flag + 'static>(mut self) -> Self {
         self.flags.push(crate::flag::FlagDef::from_trait::<F>());
         self
     }
 
+    /// Register a type-based positional argument.
+    pub fn arg<A: Arg + 'static>(mut self) -> Self {
+        self.args.push(ArgDef::from_trait::<A>());
+        self
+    }
+
+    /// Register a flag group.
+    pub fn group<G: FlagGroup + 'static>(mut self) -> Self {
+        self.groups.push(GroupDef::from_trait::<G>());
+        self
+    }
+
     /// Register a subcommand.
     pub fn subcommand<A: AppTrait + 'static>(mut self, sub: A) -> Self {
         self.subcommands.push(Box::new(sub));
@@ -106,15 +223,88 @@ impl AppTrait for App {
                 aliases: vec![],
                 value_name: None,
                 help_heading: None,
+                multiple: false,
+                value_delimiter: None,
+                count: false,
+                negatable: false,
+                missing_value: None,
+                conflicts_with: vec![],
+                requires: vec![],
+                required_if_eq: vec![],
+                required_unless_present: vec![],
+                default_value_if: vec![],
+                possible_values: vec![],
+                from_file: false,
+                sensitive: false,
+                global: false,
+                check: None,
             });
         }
+        if self.config_name.is_some() && !flags.iter().any(|f| f.name == "config") {
+            flags.push(crate::provider::FileProvider::config_flag());
+        }
+        if self.prompt_missing && !flags.iter().any(|f| f.name == crate::prompt::NO_INPUT_FLAG) {
+            flags.push(crate::prompt::no_input_flag());
+        }
+        if self.confirm.is_some() {
+            crate::confirm::add_yes_flag(&mut flags);
+        }
         flags
     }
 
+    fn args(&self) -> Vec<ArgDef> {
+        self.args.clone()
+    }
+
+    fn groups(&self) -> Vec<GroupDef> {
+        self.groups.clone()
+    }
+
     fn is_strict(&self) -> bool {
         self.strict
     }
 
+    fn config_name(&self) -> Option<&str> {
+        self.config_name.as_deref()
+    }
+
+    fn dotenv_name(&self) -> Option<&str> {
+        self.dotenv_name.as_deref()
+    }
+
+    fn env_prefix(&self) -> Option<&str> {
+        self.env_prefix.as_deref()
+    }
+
+    fn response_files(&self) -> bool {
+        self.response_files
+    }
+
+    fn infer_long_args(&self) -> bool {
+        self.infer_long_args
+    }
+
+    fn infer_subcommands(&self) -> bool {
+        self.infer_subcommands
+    }
+
+    fn external_subcommands(&self) -> bool {
+        self.external_subcommands
+    }
+
+    fn prompt_missing(&self) -> bool {
+        self.prompt_missing
+    }
+
+    fn providers(&self) -> Option<Vec<Box<dyn ValueProvider>>> {
+        self.providers.as_ref().map(|providers| {
+            providers
+                .iter()
+                .map(|p| Box::new(p.clone()) as Box<dyn ValueProvider>)
+                .collect()
+        })
+    }
+
     fn subcommands(&self) -> Vec<crate::command::CommandDef> {
         // This manual implementation of App struct is becoming tricky because it holds Box<dyn AppTrait>.
         // It needs to convert those into CommandDefs.
@@ -125,6 +315,16 @@ impl AppTrait for App {
     }
 
     fn execute(&mut self, ctx: Context) -> KoralResult<()> {
+        if self.external_subcommands && self.args.is_empty() {
+            if let Some(first) = ctx.args.first() {
+                if !self.subcommands.iter().any(|s| s.name() == first) {
+                    return crate::external::run(self, &ctx);
+                }
+            }
+        }
+        if let Some(question) = &self.confirm {
+            crate::confirm::require(question, &ctx)?;
+        }
         if let Some(action) = &self.action {
             action(ctx)
         } else {
@@ -141,6 +341,8 @@ impl std::fmt::Debug for App {
             .field("version", &self.version)
             .field("description", &self.description)
             .field("flags", &self.flags)
+            .field("args", &self.args)
+            .field("groups", &self.groups)
             // Skip subcommands if AppTrait usually doesn't strictly require Debug,
             // but subcommands usually interesting.
             // We can't debug subcommands if AppTrait doesn't require Debug.
@@ -153,6 +355,7 @@ impl std::fmt::Debug for App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::FlagValue;
 
     struct VerboseFlag;
     impl Flag for VerboseFlag {
@@ -166,6 +369,9 @@ mod tests {
         fn takes_value() -> bool {
             false
         }
+        fn from_values(values: &[&str]) -> Result<bool, String> {
+            bool::from_switch(values)
+        }
     }
 
     #[test]
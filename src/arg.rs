@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+/// Conversion check run on the values bound to an argument
+pub type ArgCheck = fn(&[&str]) -> Result<(), String>;
+
+/// Number of values a positional argument accepts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Arity {
+    /// Exactly one value.
+    One,
+    /// Zero or one value.
+    Optional,
+    /// Any number of values.
+    Many,
+}
+
+/// Internal representation of a positional argument used by Parser and App.
+#[derive(Clone, Debug)]
+pub struct ArgDef {
+    /// Name of the argument
+    pub name: String,
+    /// Help text
+    pub help: String,
+    /// Explicit position; registration order is used when absent
+    pub index: Option<usize>,
+    /// How many values the argument accepts
+    pub arity: Arity,
+    /// Value name for help/completion (e.g. "FILE")
+    pub value_name: Option<String>,
+    /// Whether at least one value is required
+    pub required: bool,
+    /// Checks that the bound values convert to the argument's type
+    pub check: Option<ArgCheck>,
+}
+
+impl ArgDef {
+    /// Create an ArgDef from an Arg trait implementation
+    pub fn from_trait<A: Arg>() -> Self {
+        Self {
+            name: A::name().to_string(),
+            help: A::help().to_string(),
+            index: A::index(),
+            arity: A::arity(),
+            value_name: A::value_name().map(|s| s.to_string()),
+            required: A::required(),
+            check: Some(check_values::<A>),
+        }
+    }
+
+    /// Placeholder shown in usage lines, e.g. `<SRC>`, `[DST]` or `<FILE>...`.
+    pub fn placeholder(&self) -> String {
+        let vname = self
+            .value_name
+            .clone()
+            .unwrap_or_else(|| self.name.to_uppercase());
+        let base = if self.required {
+            format!("<{}>", vname)
+        } else {
+            format!("[{}]", vname)
+        };
+        if self.arity == Arity::Many {
+            format!("{}...", base)
+        } else {
+            base
+        }
+    }
+}
+
+/// Trait defining a typed positional argument.
+pub trait Arg {
+    /// The type of value this argument holds.
+    ///
+    /// This is usually a [`FlagValue`](crate::traits::FlagValue), or a `Vec` of them
+    /// for arguments accepting many values.
+    type Value: Clone + Send + Sync + 'static;
+
+    /// The canonical name of the argument.
+    fn name() -> &'static str;
+
+    /// Help text.
+    fn help() -> &'static str {
+        ""
+    }
+
+    /// Explicit position among the arguments.
+    fn index() -> Option<usize> {
+        None
+    }
+
+    /// How many values the argument accepts.
+    fn arity() -> Arity {
+        Arity::One
+    }
+
+    /// Value name for help/completion hints.
+    fn value_name() -> Option<&'static str> {
+        None
+    }
+
+    /// Whether the argument is required.
+    fn required() -> bool {
+        Self::arity() == Arity::One
+    }
+
+    /// Convert the positional values bound to this argument into the typed value.
+    fn from_values(values: &[&str]) -> Result<Self::Value, String>;
+}
+
+fn check_values<A: Arg>(values: &[&str]) -> Result<(), String> {
+    A::from_values(values).map(|_| ())
+}
+
+/// Sort arguments by position: explicit `index` first, registration order otherwise.
+pub fn ordered(args: &[ArgDef]) -> Vec<ArgDef> {
+    let mut indexed: Vec<(usize, &ArgDef)> = args
+        .iter()
+        .enumerate()
+        .map(|(i, a)| (a.index.unwrap_or(i), a))
+        .collect();
+    indexed.sort_by_key(|(i, _)| *i);
+    indexed.into_iter().map(|(_, a)| a.clone()).collect()
+}
+
+/// Bind positional values to the declared arguments.
+///
+/// Optional and many-valued arguments only take what is left after every
+/// required argument that follows them has been served.
+pub fn bind(args: &[ArgDef], positionals: &[String]) -> HashMap<String, Vec<String>> {
+    let args = ordered(args);
+    let mut bound = HashMap::new();
+    let mut rest = positionals;
+
+    for (i, arg) in args.iter().enumerate() {
+        // Every required argument after this one needs at least one value
+        let reserved = args[i + 1..].iter().filter(|a| a.required).count();
+        let available = rest.len().saturating_sub(reserved);
+        let take = match arg.arity {
+            Arity::One if arg.required => rest.len().min(1),
+            Arity::One | Arity::Optional => available.min(1),
+            Arity::Many => available,
+        };
+        let (taken, remaining) = rest.split_at(take);
+        if !taken.is_empty() {
+            bound.insert(arg.name.clone(), taken.to_vec());
+        }
+        rest = remaining;
+    }
+    bound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(name: &str, arity: Arity, required: bool) -> ArgDef {
+        ArgDef {
+            name: name.to_string(),
+            help: String::new(),
+            index: None,
+            arity,
+            value_name: None,
+            required,
+            check: None,
+        }
+    }
+
+    fn strings(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_bind_many_before_required() {
+        let args = vec![def("src", Arity::Many, true), def("dst", Arity::One, true)];
+        let bound = bind(&args, &strings(&["a", "b", "c"]));
+        assert_eq!(bound["src"], strings(&["a", "b"]));
+        assert_eq!(bound["dst"], strings(&["c"]));
+    }
+
+    #[test]
+    fn test_bind_optional_left_empty() {
+        let args = vec![
+            def("src", Arity::One, true),
+            def("dst", Arity::Optional, false),
+        ];
+        let bound = bind(&args, &strings(&["a"]));
+        assert_eq!(bound["src"], strings(&["a"]));
+        assert!(!bound.contains_key("dst"));
+    }
+
+    #[test]
+    fn test_placeholder() {
+        assert_eq!(def("src", Arity::One, true).placeholder(), "<SRC>");
+        assert_eq!(def("dst", Arity::Optional, false).placeholder(), "[DST]");
+        assert_eq!(def("files", Arity::Many, false).placeholder(), "[FILES]...");
+    }
+}
@@ -11,6 +11,10 @@ pub struct CommandDef {
     pub subcommands: Vec<CommandDef>,
     /// Flags for this command
     pub flags: Vec<crate::flag::FlagDef>,
+    /// Positional arguments for this command
+    pub args: Vec<crate::arg::ArgDef>,
+    /// Whether this is an external subcommand found on `PATH`
+    pub external: bool,
 }
 
 impl CommandDef {
@@ -22,6 +26,8 @@ impl CommandDef {
             aliases: vec![],
             subcommands: vec![],
             flags: vec![],
+            args: vec![],
+            external: false,
         }
     }
 
@@ -42,4 +48,51 @@ impl CommandDef {
         self.flags = flags;
         self
     }
+
+    /// Set positional arguments
+    pub fn with_args(mut self, args: Vec<crate::arg::ArgDef>) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Set whether this is an external subcommand
+    pub fn with_external(mut self, external: bool) -> Self {
+        self.external = external;
+        self
+    }
+}
+
+/// Find the subcommand called `name`, or one of its aliases.
+///
+/// With `infer`, an unambiguous prefix of a name or alias also matches;
+/// an ambiguous prefix is an error listing the candidates.
+pub fn resolve_subcommand<'a>(
+    subcommands: &'a [CommandDef],
+    name: &str,
+    infer: bool,
+) -> crate::KoralResult<Option<&'a CommandDef>> {
+    let exact = subcommands
+        .iter()
+        .find(|s| s.name == name || s.aliases.iter().any(|a| a == name));
+    if exact.is_some() || !infer || name.is_empty() {
+        return Ok(exact);
+    }
+
+    let candidates: Vec<&CommandDef> = subcommands
+        .iter()
+        .filter(|s| s.name.starts_with(name) || s.aliases.iter().any(|a| a.starts_with(name)))
+        .collect();
+    match candidates.as_slice() {
+        [] => Ok(None),
+        [sub] => Ok(Some(sub)),
+        _ => Err(crate::KoralError::InvalidSubcommand(format!(
+            "Command '{}' is ambiguous\n\t[candidates: {}]",
+            name,
+            candidates
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
 }
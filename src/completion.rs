@@ -54,13 +54,7 @@ fn generate_bash<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
     writeln!(buf, "    case \"$cmd\" in")?;
 
     // Collect all commands (root + recursive)
-    let root = crate::command::CommandDef {
-        name: name.to_string(),
-        description: app.description().to_string(),
-        aliases: vec![],
-        subcommands: app.subcommands(),
-        flags: app.flags(),
-    };
+    let root = command_tree(app);
 
     // Helper to linearize commands
     fn collect_cmds(
@@ -90,6 +84,9 @@ fn generate_bash<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
         let mut opts = Vec::new();
         for flag in &cmd.flags {
             opts.push(format!("--{}", flag.name));
+            if flag.negatable {
+                opts.push(format!("--no-{}", flag.name));
+            }
             if let Some(s) = flag.short {
                 opts.push(format!("-{}", s));
             }
@@ -99,11 +96,51 @@ fn generate_bash<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
         }
 
         writeln!(buf, "        {})", prefix)?;
+        // Complete the value of a flag restricted to possible values
+        let restricted: Vec<_> = cmd
+            .flags
+            .iter()
+            .filter(|f| f.takes_value && !f.possible_values.is_empty())
+            .collect();
+        if !restricted.is_empty() {
+            writeln!(buf, "            case \"${{prev}}\" in")?;
+            for flag in restricted {
+                let mut names = vec![format!("--{}", flag.name)];
+                if let Some(s) = flag.short {
+                    names.push(format!("-{}", s));
+                }
+                writeln!(buf, "                {})", names.join("|"))?;
+                writeln!(
+                    buf,
+                    "                    COMPREPLY=( $(compgen -W \"{}\" -- ${{cur}}) )",
+                    flag.possible_values.join(" ")
+                )?;
+                writeln!(buf, "                    return 0")?;
+                writeln!(buf, "                    ;;")?;
+            }
+            writeln!(buf, "            esac")?;
+        }
         writeln!(buf, "            opts=\"{}\"", opts.join(" "))?;
         writeln!(
             buf,
             "            COMPREPLY=( $(compgen -W \"${{opts}}\" -- ${{cur}}) )"
         )?;
+        // Positional arguments naming files or directories
+        let hints: Vec<PathHint> = cmd.args.iter().filter_map(arg_path_hint).collect();
+        if !hints.is_empty() {
+            let compgen = if hints.contains(&PathHint::File) {
+                "-f"
+            } else {
+                "-d"
+            };
+            writeln!(buf, "            if [[ ${{cur}} != -* ]]; then")?;
+            writeln!(
+                buf,
+                "                COMPREPLY+=( $(compgen {} -- ${{cur}}) )",
+                compgen
+            )?;
+            writeln!(buf, "            fi")?;
+        }
         writeln!(buf, "            ;;")?;
     }
 
@@ -120,13 +157,7 @@ fn generate_zsh<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
     let name = app.name();
     writeln!(buf, "#compdef {}", name)?;
 
-    let root = crate::command::CommandDef {
-        name: name.to_string(),
-        description: app.description().to_string(),
-        aliases: vec![],
-        subcommands: app.subcommands(),
-        flags: app.flags(),
-    };
+    let root = command_tree(app);
 
     // Recursive function generator
     fn write_zsh_func<W: Write>(
@@ -144,20 +175,68 @@ fn generate_zsh<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
             let mut arg_spec = String::new();
             if flag.takes_value {
                 let vname = flag.value_name.as_deref().unwrap_or("value");
-                let v_upper = vname.to_uppercase();
-                let action = if v_upper.contains("FILE") || v_upper.contains("PATH") {
-                    ":_files"
-                } else if v_upper.contains("DIR") {
-                    ":_files -/"
+                let action = if !flag.possible_values.is_empty() {
+                    format!(":({})", flag.possible_values.join(" "))
                 } else {
-                    ""
+                    match path_hint(vname) {
+                        Some(hint) => format!(":{}", hint.zsh_action()),
+                        None => String::new(),
+                    }
                 };
                 arg_spec = format!(":{}{}", vname, action);
             }
+            // Optional values must be attached: `-cVALUE` or `--flag=VALUE`
+            let (short_sep, long_sep) = if flag.missing_value.is_some() {
+                arg_spec.insert(0, ':');
+                ("-", "=-")
+            } else {
+                ("", "")
+            };
+            // Repeatable flags may be completed again after being used
+            let repeat = if flag.multiple || flag.count { "*" } else { "" };
             if let Some(s) = flag.short {
-                writeln!(buf, "        '-{}[{}]{}'", s, help, arg_spec)?;
+                writeln!(
+                    buf,
+                    "        '{}-{}{}[{}]{}'",
+                    repeat, s, short_sep, help, arg_spec
+                )?;
+            }
+            writeln!(
+                buf,
+                "        '{}--{}{}[{}]{}'",
+                repeat, flag.name, long_sep, help, arg_spec
+            )?;
+            if flag.negatable {
+                writeln!(buf, "        '--no-{}[Disable --{}]'", flag.name, flag.name)?;
+            }
+        }
+
+        // Positional arguments (only when they are not shadowed by subcommands)
+        if cmd.subcommands.is_empty() {
+            for (i, arg) in crate::arg::ordered(&cmd.args).iter().enumerate() {
+                let vname = arg
+                    .value_name
+                    .clone()
+                    .unwrap_or_else(|| arg.name.to_uppercase());
+                let action = arg_path_hint(arg).map(|h| h.zsh_action()).unwrap_or(" ");
+                let position = match arg.arity {
+                    crate::arg::Arity::Many => "*".to_string(),
+                    _ => (i + 1).to_string(),
+                };
+                let optional = if arg.required || arg.arity == crate::arg::Arity::Many {
+                    ""
+                } else {
+                    ":"
+                };
+                writeln!(
+                    buf,
+                    "        '{}:{}{}:{}'",
+                    position,
+                    optional,
+                    escape_help(&vname),
+                    action
+                )?;
             }
-            writeln!(buf, "        '--{}[{}]{}'", flag.name, help, arg_spec)?;
         }
 
         // Subcommands
@@ -215,13 +294,7 @@ fn generate_zsh<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
 
 fn generate_fish<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
     let name = app.name();
-    let root = crate::command::CommandDef {
-        name: name.to_string(),
-        description: app.description().to_string(),
-        aliases: vec![],
-        subcommands: app.subcommands(),
-        flags: app.flags(),
-    };
+    let root = command_tree(app);
 
     fn write_fish_cmd<W: Write>(
         buf: &mut W,
@@ -274,7 +347,15 @@ fn generate_fish<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
                 for sub in &cmd.subcommands {
                     line.push_str(&format!("{} ", sub.name));
                 }
-                line.push_str("\"");
+                line.push('"');
+            }
+
+            if flag.negatable {
+                writeln!(
+                    buf,
+                    "{} -l no-{} -d 'Disable --{}'",
+                    line, flag.name, flag.name
+                )?;
             }
 
             if let Some(s) = flag.short {
@@ -282,9 +363,22 @@ fn generate_fish<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
             }
             line.push_str(&format!(" -l {}", flag.name));
             line.push_str(&format!(" -d '{}'", help));
-            if flag.takes_value {
+            if flag.takes_value && flag.missing_value.is_none() {
                 line.push_str(" -r");
             }
+            if flag.takes_value && !flag.possible_values.is_empty() {
+                line.push_str(&format!(" -f -a '{}'", flag.possible_values.join(" ")));
+            }
+            writeln!(buf, "{}", line)?;
+        }
+
+        // Positional arguments naming files or directories
+        if cmd.args.iter().any(|a| arg_path_hint(a).is_some()) {
+            let mut line = format!("complete -c {}", root_name);
+            if !path.is_empty() {
+                line.push_str(&format!(" -n '{}'", condition));
+            }
+            line.push_str(" -F");
             writeln!(buf, "{}", line)?;
         }
 
@@ -309,6 +403,61 @@ fn generate_fish<W: Write>(app: &impl App, buf: &mut W) -> io::Result<()> {
     Ok(())
 }
 
+/// The command tree of `app`, with global flags repeated in every subcommand below them.
+pub(crate) fn command_tree<A: App + ?Sized>(app: &A) -> crate::command::CommandDef {
+    let mut root = crate::command::CommandDef::new(app.name(), app.description())
+        .with_subcommands(crate::external::with_plugins(app))
+        .with_flags(app.flags())
+        .with_args(app.args());
+    propagate_globals(&mut root, &[]);
+    root
+}
+
+fn propagate_globals(cmd: &mut crate::command::CommandDef, inherited: &[crate::flag::FlagDef]) {
+    for global in inherited {
+        if !cmd.flags.iter().any(|f| f.name == global.name) {
+            cmd.flags.push(global.clone());
+        }
+    }
+    let globals: Vec<crate::flag::FlagDef> =
+        cmd.flags.iter().filter(|f| f.global).cloned().collect();
+    for sub in &mut cmd.subcommands {
+        propagate_globals(sub, &globals);
+    }
+}
+
+/// Kind of filesystem path a value name refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathHint {
+    File,
+    Dir,
+}
+
+impl PathHint {
+    fn zsh_action(self) -> &'static str {
+        match self {
+            PathHint::File => "_files",
+            PathHint::Dir => "_files -/",
+        }
+    }
+}
+
+/// Guess from a value name (e.g. "FILE", "out_dir") whether it is a path.
+fn path_hint(value_name: &str) -> Option<PathHint> {
+    let upper = value_name.to_uppercase();
+    if upper.contains("FILE") || upper.contains("PATH") {
+        Some(PathHint::File)
+    } else if upper.contains("DIR") {
+        Some(PathHint::Dir)
+    } else {
+        None
+    }
+}
+
+fn arg_path_hint(arg: &crate::arg::ArgDef) -> Option<PathHint> {
+    path_hint(arg.value_name.as_deref().unwrap_or(&arg.name))
+}
+
 fn escape_help(s: &str) -> String {
     s.replace("'", "'\\''")
 }
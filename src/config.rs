@@ -0,0 +1,222 @@
+use crate::error::{KoralError, KoralResult};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Supported configuration file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// TOML (requires the `toml` feature)
+    Toml,
+    /// JSON (requires the `json` feature)
+    Json,
+    /// INI (`key = value` lines under `[section]` headers)
+    Ini,
+}
+
+impl ConfigFormat {
+    /// Guess the format from a file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "toml" => Some(ConfigFormat::Toml),
+            "json" => Some(ConfigFormat::Json),
+            "ini" | "cfg" | "conf" => Some(ConfigFormat::Ini),
+            _ => None,
+        }
+    }
+}
+
+/// Flattened configuration values.
+///
+/// Keys of nested tables are joined with dots, so `region` under `[deploy]`
+/// is stored as `deploy.region`. Arrays keep every element.
+pub(crate) type ConfigValues = HashMap<String, Vec<String>>;
+
+/// Parse configuration content in the given format.
+pub(crate) fn parse(content: &str, format: ConfigFormat) -> KoralResult<ConfigValues> {
+    match format {
+        ConfigFormat::Toml => parse_toml(content),
+        ConfigFormat::Json => parse_json(content),
+        ConfigFormat::Ini => Ok(parse_ini(content)),
+    }
+}
+
+/// Find the configuration file for an application.
+///
+/// Looks at `--config <path>` in `args`, then the `<NAME>_CONFIG` environment
+/// variable, then `config.{toml,json,ini}` in `$XDG_CONFIG_HOME/<name>`
+/// (or `~/.config/<name>`).
+pub(crate) fn locate(app_name: &str, args: &[String]) -> Option<PathBuf> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == "--config" {
+            if let Some(path) = iter.next() {
+                return Some(crate::host::resolve(path));
+            }
+        } else if let Some(path) = arg.strip_prefix("--config=") {
+            return Some(crate::host::resolve(path));
+        }
+    }
+
+    let env_var = format!("{}_CONFIG", app_name.to_uppercase().replace('-', "_"));
+    if let Some(path) = crate::host::var(&env_var) {
+        if !path.is_empty() {
+            return Some(crate::host::resolve(path));
+        }
+    }
+
+    let base = crate::host::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| crate::host::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    ["toml", "json", "ini"]
+        .iter()
+        .map(|ext| base.join(app_name).join(format!("config.{}", ext)))
+        .find(|p| p.is_file())
+}
+
+fn parse_ini(content: &str) -> ConfigValues {
+    let mut values = ConfigValues::new();
+    let mut section = String::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+            continue;
+        }
+        if let Some((key, val)) = line.split_once('=') {
+            let val = val.trim();
+            let val = val
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(val);
+            values
+                .entry(join_key(&section, key.trim()))
+                .or_default()
+                .push(val.to_string());
+        }
+    }
+    values
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml(content: &str) -> KoralResult<ConfigValues> {
+    fn flatten(prefix: &str, table: &toml::Table, out: &mut ConfigValues) {
+        for (key, value) in table {
+            let key = join_key(prefix, key);
+            match value {
+                toml::Value::Table(t) => flatten(&key, t, out),
+                toml::Value::Array(items) => {
+                    out.insert(key, items.iter().map(scalar).collect());
+                }
+                other => {
+                    out.insert(key, vec![scalar(other)]);
+                }
+            }
+        }
+    }
+
+    fn scalar(value: &toml::Value) -> String {
+        match value {
+            toml::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    let table: toml::Table = content
+        .parse()
+        .map_err(|e| KoralError::Validation(format!("Invalid TOML config: {}", e)))?;
+    let mut values = ConfigValues::new();
+    flatten("", &table, &mut values);
+    Ok(values)
+}
+
+#[cfg(not(feature = "toml"))]
+fn parse_toml(_content: &str) -> KoralResult<ConfigValues> {
+    Err(KoralError::Validation(
+        "TOML config files require the `toml` feature".to_string(),
+    ))
+}
+
+#[cfg(feature = "json")]
+fn parse_json(content: &str) -> KoralResult<ConfigValues> {
+    use serde_json::Value;
+
+    fn flatten(prefix: &str, object: &serde_json::Map<String, Value>, out: &mut ConfigValues) {
+        for (key, value) in object {
+            let key = join_key(prefix, key);
+            match value {
+                Value::Object(o) => flatten(&key, o, out),
+                Value::Array(items) => {
+                    out.insert(key, items.iter().map(scalar).collect());
+                }
+                Value::Null => {}
+                other => {
+                    out.insert(key, vec![scalar(other)]);
+                }
+            }
+        }
+    }
+
+    fn scalar(value: &Value) -> String {
+        match value {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        }
+    }
+
+    let value: Value = serde_json::from_str(content)
+        .map_err(|e| KoralError::Validation(format!("Invalid JSON config: {}", e)))?;
+    let mut values = ConfigValues::new();
+    match &value {
+        Value::Object(o) => flatten("", o, &mut values),
+        _ => {
+            return Err(KoralError::Validation(
+                "JSON config must be an object".to_string(),
+            ))
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(not(feature = "json"))]
+fn parse_json(_content: &str) -> KoralResult<ConfigValues> {
+    Err(KoralError::Validation(
+        "JSON config files require the `json` feature".to_string(),
+    ))
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ini_sections() {
+        let values = parse_ini("# comment\nregion = us-east-1\n\n[deploy]\nreplicas = \"3\"\n");
+        assert_eq!(values["region"], vec!["us-east-1"]);
+        assert_eq!(values["deploy.replicas"], vec!["3"]);
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("a/config.TOML")),
+            Some(ConfigFormat::Toml)
+        );
+        assert_eq!(ConfigFormat::from_path(Path::new("config")), None);
+    }
+}
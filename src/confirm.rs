@@ -0,0 +1,156 @@
+use crate::context::Context;
+use crate::error::{KoralError, KoralResult};
+use crate::extract::FromContext;
+use crate::flag::FlagDef;
+use crate::prompt::PromptHandle;
+
+/// Name of the flag answering yes to every confirmation.
+pub const YES_FLAG: &str = "yes";
+
+/// Extractor asking the user to confirm an action.
+///
+/// `--yes` (or `<PREFIX>_ASSUME_YES`) confirms without asking. Without a
+/// terminal to ask on, or with `--no-input`, confirmations fail instead.
+/// `#[app(confirm)]` adds the `--yes` flag to a command.
+#[derive(Debug, Clone)]
+pub struct Confirm {
+    assume_yes: bool,
+    no_input: bool,
+    io: PromptHandle,
+}
+
+impl Confirm {
+    /// Ask `question`, returning whether it was accepted.
+    pub fn ask(&self, question: &str) -> KoralResult<bool> {
+        if self.assume_yes {
+            return Ok(true);
+        }
+        let mut io = self.io.borrow_mut();
+        if self.no_input || !io.is_interactive() {
+            return Err(KoralError::Aborted(format!(
+                "{} Pass --yes to confirm without a terminal",
+                question
+            )));
+        }
+        Ok(crate::prompt::ask_yes_no(question, &mut *io)?.unwrap_or(false))
+    }
+
+    /// Ask `question`, failing unless it is accepted.
+    pub fn require(&self, question: &str) -> KoralResult<()> {
+        if self.ask(question)? {
+            Ok(())
+        } else {
+            Err(KoralError::Aborted("Not confirmed".to_string()))
+        }
+    }
+}
+
+impl<'a> FromContext<'a> for Confirm {
+    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
+        Ok(Confirm {
+            assume_yes: ctx.is_present(YES_FLAG),
+            no_input: ctx.is_present(crate::prompt::NO_INPUT_FLAG),
+            io: PromptHandle::of(ctx),
+        })
+    }
+}
+
+/// Ask the question of `#[app(confirm = "...")]` before the handler runs.
+///
+/// `{name}` in `template` is replaced by the value of the flag or argument `name`.
+#[doc(hidden)]
+pub fn require(template: &str, ctx: &Context) -> KoralResult<()> {
+    Confirm::from_context(ctx)?.require(&render(template, ctx))
+}
+
+/// Add the `--yes` flag, with `-y` unless another flag already uses it.
+#[doc(hidden)]
+pub fn add_yes_flag(flags: &mut Vec<FlagDef>) {
+    if flags.iter().any(|f| f.name == YES_FLAG) {
+        return;
+    }
+    let short = (!flags.iter().any(|f| f.short == Some('y'))).then_some('y');
+    flags.push(FlagDef {
+        name: YES_FLAG.to_string(),
+        long: Some(YES_FLAG.to_string()),
+        short,
+        help: "Answer yes to confirmations".to_string(),
+        takes_value: false,
+        required: false,
+        default_value: None,
+        env: None,
+        validator: None,
+        aliases: vec!["assume-yes".to_string()],
+        value_name: None,
+        help_heading: None,
+        multiple: false,
+        value_delimiter: None,
+        count: false,
+        negatable: false,
+        missing_value: None,
+        conflicts_with: vec![],
+        requires: vec![],
+        required_if_eq: vec![],
+        required_unless_present: vec![],
+        default_value_if: vec![],
+        possible_values: vec![],
+        from_file: false,
+        sensitive: false,
+        global: false,
+        check: None,
+    });
+}
+
+/// Let `--yes` read `<PREFIX>_ASSUME_YES`, whichever command declares it.
+pub(crate) fn apply_assume_yes_env(flags: &mut [FlagDef], root_prefix: &str) {
+    if let Some(yes) = flags
+        .iter_mut()
+        .find(|f| f.name == YES_FLAG && f.env.is_none())
+    {
+        yes.env = Some(format!("{}_ASSUME_YES", root_prefix));
+    }
+}
+
+/// Replace each `{name}` by the values of the flag or argument `name`.
+/// Unknown names are left as they are.
+fn render(template: &str, ctx: &Context) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        out.push_str(&rest[..start]);
+        if ctx.is_present(name) {
+            out.push_str(&ctx.values_of(name).join(","));
+        } else if let Some(values) = ctx.arg_values.get(name) {
+            out.push_str(&values.join(" "));
+        } else {
+            out.push_str(&rest[start..=start + len]);
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_render_flags_and_args() {
+        let mut ctx = Context::new(
+            HashMap::from([("region".to_string(), Some("eu".to_string()))]),
+            vec![],
+        );
+        ctx.arg_values
+            .insert("name".to_string(), vec!["prod".to_string()]);
+        assert_eq!(
+            render("Delete cluster {name} in {region} ({missing})?", &ctx),
+            "Delete cluster prod in eu ({missing})?"
+        );
+    }
+}
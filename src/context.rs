@@ -1,6 +1,8 @@
+use crate::provider::ValueSource;
 use crate::traits::FlagValue;
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
 /// Result of parsing command line arguments.
 /// Result of parsing command line arguments.
@@ -10,9 +12,21 @@ pub struct Context<'a, A: ?Sized = dyn Any> {
     /// Value is the string representation of the value, or empty string for boolean flags.
     pub flags: HashMap<String, Option<String>>,
 
+    /// Every value given for flags that accept multiple values, in order.
+    pub values: HashMap<String, Vec<String>>,
+
+    /// Where each flag value came from. Key is the flag name.
+    pub sources: HashMap<String, ValueSource>,
+
+    /// Names of flags whose values are masked in debug and provenance output.
+    pub sensitive: HashSet<String>,
+
     /// Positional arguments.
     pub args: Vec<String>,
 
+    /// Positional values bound to declared arguments. Key is the argument name.
+    pub arg_values: HashMap<String, Vec<String>>,
+
     /// Reference to the current command instance (e.g. AddCmd).
     pub app: Option<&'a mut A>,
 
@@ -21,8 +35,16 @@ pub struct Context<'a, A: ?Sized = dyn Any> {
 
     /// Type-safe extensions map for middleware to inject data.
     pub extensions: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+
+    /// State handed down to subcommands during dispatch.
+    #[doc(hidden)]
+    pub inherited: crate::traits::Inherited,
+
+    /// Context of the command that dispatched to this one.
+    pub(crate) parent: Option<Rc<Context<'static>>>,
 }
 
+use crate::arg::Arg;
 use crate::flag::Flag;
 
 impl<'a, A: ?Sized> Context<'a, A> {
@@ -30,10 +52,16 @@ impl<'a, A: ?Sized> Context<'a, A> {
     pub fn new(flags: HashMap<String, Option<String>>, args: Vec<String>) -> Self {
         Self {
             flags,
+            values: HashMap::new(),
+            sources: HashMap::new(),
+            sensitive: HashSet::new(),
             args,
+            arg_values: HashMap::new(),
             app: None,
             state: None,
             extensions: HashMap::new(),
+            inherited: Default::default(),
+            parent: None,
         }
     }
 
@@ -43,23 +71,169 @@ impl<'a, A: ?Sized> Context<'a, A> {
         self
     }
 
+    /// Set the values collected for multi-valued flags.
+    pub fn with_values(mut self, values: HashMap<String, Vec<String>>) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Set where each flag value came from.
+    pub fn with_sources(mut self, sources: HashMap<String, ValueSource>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Set the names of flags whose values are masked.
+    pub fn with_sensitive(mut self, sensitive: HashSet<String>) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// Set the positional values bound to declared arguments.
+    pub fn with_arg_values(mut self, arg_values: HashMap<String, Vec<String>>) -> Self {
+        self.arg_values = arg_values;
+        self
+    }
+
+    /// Copy the parsed flags, arguments and parent into a fresh context,
+    /// without app, state or extensions.
+    pub(crate) fn snapshot<'b>(&self) -> Context<'b> {
+        Context::new(self.flags.clone(), self.args.clone())
+            .with_values(self.values.clone())
+            .with_sources(self.sources.clone())
+            .with_sensitive(self.sensitive.clone())
+            .with_arg_values(self.arg_values.clone())
+            .with_parent(self.parent.clone())
+    }
+
+    pub(crate) fn with_parent(mut self, parent: Option<Rc<Context<'static>>>) -> Self {
+        self.parent = parent;
+        self
+    }
+
+    /// Context of the command that dispatched to this one, if any.
+    ///
+    /// Its own `parent()` leads further up to the root command.
+    pub fn parent(&self) -> Option<&Context<'static>> {
+        self.parent.as_deref()
+    }
+
+    /// Hand this context down to a subcommand: returns the shared state and
+    /// what the subcommand inherits, with this context as its parent.
+    #[doc(hidden)]
+    pub fn into_dispatch(self) -> (Option<&'a mut dyn Any>, crate::traits::Inherited) {
+        let parent = Context {
+            flags: self.flags,
+            values: self.values,
+            sources: self.sources,
+            sensitive: self.sensitive,
+            args: self.args,
+            arg_values: self.arg_values,
+            app: None,
+            state: None,
+            extensions: self.extensions,
+            inherited: Default::default(),
+            parent: self.parent,
+        };
+        let mut inherited = self.inherited;
+        inherited.parent = Some(Rc::new(parent));
+        (self.state, inherited)
+    }
+
     /// Set the shared state reference.
     pub fn with_state(mut self, state: &'a mut dyn Any) -> Self {
         self.state = Some(state);
         self
     }
 
+    pub(crate) fn with_state_opt(mut self, state: Option<&'a mut dyn Any>) -> Self {
+        self.state = state;
+        self
+    }
+
+    pub(crate) fn with_inherited(mut self, inherited: crate::traits::Inherited) -> Self {
+        self.inherited = inherited;
+        self
+    }
+
+    /// Take the values a parent parsed for global flags not given to this command.
+    pub(crate) fn merge_globals(&mut self, parent: crate::traits::GlobalValues) {
+        for (name, value) in parent.flags {
+            if self.sources.get(&name) == Some(&ValueSource::CommandLine) {
+                continue;
+            }
+            match parent.values.get(&name) {
+                Some(values) => self.values.insert(name.clone(), values.clone()),
+                None => self.values.remove(&name),
+            };
+            match parent.sources.get(&name) {
+                Some(source) => self.sources.insert(name.clone(), source.clone()),
+                None => self.sources.remove(&name),
+            };
+            self.flags.insert(name, value);
+        }
+    }
+
+    /// Values of the given global flags, to hand down to a subcommand.
+    pub(crate) fn global_values(
+        &self,
+        globals: &[crate::flag::FlagDef],
+    ) -> crate::traits::GlobalValues {
+        let mut out = crate::traits::GlobalValues::default();
+        for name in globals.iter().map(|g| &g.name) {
+            if let Some(value) = self.flags.get(name) {
+                out.flags.insert(name.clone(), value.clone());
+            }
+            if let Some(values) = self.values.get(name) {
+                out.values.insert(name.clone(), values.clone());
+            }
+            if let Some(source) = self.sources.get(name) {
+                out.sources.insert(name.clone(), source.clone());
+            }
+        }
+        out
+    }
+
     /// Check if a flag was present.
     pub fn is_present(&self, name: &str) -> bool {
         self.flags.contains_key(name)
     }
 
+    /// Where the value of a flag came from, if it has one.
+    pub fn source_of(&self, name: &str) -> Option<ValueSource> {
+        self.sources.get(name).cloned()
+    }
+
+    /// Describe a flag value and its source, e.g. `region=us-east-1 (from env CLOUD_REGION)`.
+    ///
+    /// Values of sensitive flags are masked.
+    pub fn describe(&self, name: &str) -> Option<String> {
+        let value = match self.values.get(name) {
+            Some(values) => values.join(","),
+            None => self
+                .flags
+                .get(name)?
+                .clone()
+                .unwrap_or_else(|| "true".to_string()),
+        };
+        let value = if self.sensitive.contains(name) {
+            MASK.to_string()
+        } else {
+            value
+        };
+        Some(match self.sources.get(name) {
+            Some(source) => format!("{}={} (from {})", name, value, source),
+            None => format!("{}={}", name, value),
+        })
+    }
+
     /// Get typed flag value using the Flag trait.
-    pub fn get<F: Flag>(&self) -> Option<F::Value>
-    where
-        <F::Value as std::str::FromStr>::Err: std::fmt::Display,
-    {
-        self.value_t::<F::Value>(F::name()).ok()
+    /// Falls back to the flag's default value when it was not provided.
+    pub fn get<F: Flag>(&self) -> Option<F::Value> {
+        if !self.flags.contains_key(F::name()) {
+            return F::default_value();
+        }
+        F::from_values(&self.values_of(F::name())).ok()
     }
 
     /// Get raw flag value as string, if present.
@@ -67,6 +241,32 @@ impl<'a, A: ?Sized> Context<'a, A> {
         self.flags.get(name).and_then(|opt| opt.as_deref())
     }
 
+    /// Get every raw value given for a flag.
+    /// Single-valued flags yield at most their last value.
+    pub fn values_of(&self, name: &str) -> Vec<&str> {
+        match self.values.get(name) {
+            Some(values) => values.iter().map(|v| v.as_str()).collect(),
+            None => self.value_of(name).into_iter().collect(),
+        }
+    }
+
+    /// Get typed positional argument value, if it was given.
+    pub fn arg<T: Arg>(&self) -> Option<T::Value> {
+        let values = self.arg_values_of(T::name());
+        if values.is_empty() {
+            return None;
+        }
+        T::from_values(&values).ok()
+    }
+
+    /// Get the raw positional values bound to an argument.
+    pub fn arg_values_of(&self, name: &str) -> Vec<&str> {
+        self.arg_values
+            .get(name)
+            .map(|values| values.iter().map(|v| v.as_str()).collect())
+            .unwrap_or_default()
+    }
+
     /// Get typed flag value.
     pub fn value_t<T: FlagValue>(&self, name: &str) -> Result<T, String>
     where
@@ -89,6 +289,11 @@ impl<'a, A: ?Sized> Context<'a, A> {
         }
     }
 
+    /// Where this command writes its output and reads its answers.
+    pub fn terminal(&self) -> crate::output::Terminal {
+        self.inherited.terminal()
+    }
+
     // Deprecated or alias helper
     /// Check if a flag is present (alias for is_present).
     pub fn has_flag(&self, name: &str) -> bool {
@@ -119,10 +324,13 @@ impl<'a, A: ?Sized> Context<'a, A> {
     }
 
     /// Get a reference to an extension.
+    ///
+    /// Extensions inserted for parent commands are found too, the nearest first.
     pub fn get_extension<T: Any + Send + Sync>(&self) -> Option<&T> {
         self.extensions
             .get(&TypeId::of::<T>())
             .and_then(|boxed| boxed.downcast_ref())
+            .or_else(|| self.parent()?.get_extension())
     }
 
     /// Get a mutable reference to an extension.
@@ -171,14 +379,43 @@ impl<'a> Context<'a, dyn Any> {
     }
 }
 
+/// Replacement shown for sensitive values.
+const MASK: &str = "********";
+
 impl<'a, A: ?Sized> std::fmt::Debug for Context<'a, A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flags: HashMap<&String, Option<&str>> = self
+            .flags
+            .iter()
+            .map(|(name, value)| {
+                let masked = self.sensitive.contains(name) && value.is_some();
+                (name, if masked { Some(MASK) } else { value.as_deref() })
+            })
+            .collect();
+        let values: HashMap<&String, Vec<&str>> = self
+            .values
+            .iter()
+            .map(|(name, values)| {
+                let masked = self.sensitive.contains(name);
+                (
+                    name,
+                    values
+                        .iter()
+                        .map(|v| if masked { MASK } else { v.as_str() })
+                        .collect(),
+                )
+            })
+            .collect();
         f.debug_struct("Context")
-            .field("flags", &self.flags)
+            .field("flags", &flags)
+            .field("values", &values)
+            .field("sources", &self.sources)
             .field("args", &self.args)
+            .field("arg_values", &self.arg_values)
             .field("app", &"...")
             .field("state", &"...")
             .field("extensions", &self.extensions.keys())
+            .field("parent", &self.parent)
             .finish()
     }
 }
@@ -187,10 +424,16 @@ impl<'a, A: ?Sized> Default for Context<'a, A> {
     fn default() -> Self {
         Self {
             flags: HashMap::new(),
+            values: HashMap::new(),
+            sources: HashMap::new(),
+            sensitive: HashSet::new(),
             args: Vec::new(),
+            arg_values: HashMap::new(),
             app: None,
             state: None,
             extensions: HashMap::new(),
+            inherited: Default::default(),
+            parent: None,
         }
     }
 }
@@ -12,6 +12,12 @@ pub enum KoralError {
     /// Invalid flag or command specified
     #[error("Invalid flag/command: {0}")]
     InvalidFlag(String),
+    /// Positional argument that no declared argument accepts
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    /// Subcommand that cannot be resolved, e.g. an ambiguous prefix
+    #[error("Invalid subcommand: {0}")]
+    InvalidSubcommand(String),
     /// Unknown flag encountered
     #[error("Unknown flag: {0}")]
     UnknownFlag(String),
@@ -21,11 +27,35 @@ pub enum KoralError {
     /// IO error
     #[error("IO error: {0}")]
     IoError(String),
+    /// A confirmation was declined or could not be asked
+    #[error("Aborted: {0}")]
+    Aborted(String),
+    /// External subcommand exited with a non-zero status
+    #[error("External command '{0}' exited with status {1}")]
+    ExternalCommandFailed(String, i32),
     /// Other custom error
     #[error(transparent)]
     Other(#[from] Box<dyn std::error::Error + Send + Sync>),
 }
 
+impl KoralError {
+    /// Exit status a process should end with: 2 for usage errors, the command's
+    /// own status for failed external subcommands, 1 otherwise.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            KoralError::FlagValueParseError(_)
+            | KoralError::MissingArgument(_)
+            | KoralError::InvalidFlag(_)
+            | KoralError::InvalidArgument(_)
+            | KoralError::InvalidSubcommand(_)
+            | KoralError::UnknownFlag(_)
+            | KoralError::Validation(_) => 2,
+            KoralError::ExternalCommandFailed(_, code) => *code,
+            _ => 1,
+        }
+    }
+}
+
 impl From<std::io::Error> for KoralError {
     fn from(err: std::io::Error) -> Self {
         KoralError::IoError(err.to_string())
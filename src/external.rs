@@ -0,0 +1,154 @@
+use crate::command::CommandDef;
+use crate::error::{KoralError, KoralResult};
+use crate::extract::FromContext;
+use crate::provider::ValueSource;
+use crate::traits::App;
+use crate::Context;
+use std::path::{Path, PathBuf};
+
+/// Extractor for an unrecognised subcommand and the arguments following it.
+///
+/// Handlers given to `#[app(external_subcommands = handler)]` receive it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalCommand {
+    /// Name the subcommand was invoked with
+    pub name: String,
+    /// Arguments following the name, untouched
+    pub args: Vec<String>,
+}
+
+impl<'a> FromContext<'a> for ExternalCommand {
+    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
+        match ctx.args.split_first() {
+            Some((name, args)) => Ok(ExternalCommand {
+                name: name.clone(),
+                args: args.to_vec(),
+            }),
+            None => Err(KoralError::MissingArgument(
+                "No subcommand provided".to_string(),
+            )),
+        }
+    }
+}
+
+/// Find the executable `<app_name>-<name>` on `PATH`.
+pub fn find(app_name: &str, name: &str) -> Option<PathBuf> {
+    let file = format!("{}-{}{}", app_name, name, std::env::consts::EXE_SUFFIX);
+    let path = crate::host::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(&file))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// List the `<app_name>-*` executables on `PATH` as subcommands.
+///
+/// Names already taken by `known` commands are skipped, as are duplicates
+/// found later on `PATH`.
+pub fn discover(app_name: &str, known: &[CommandDef]) -> Vec<CommandDef> {
+    let prefix = format!("{}-", app_name);
+    let mut found: Vec<CommandDef> = Vec::new();
+    let Some(path) = crate::host::var_os("PATH") else {
+        return found;
+    };
+    for dir in std::env::split_paths(&path) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let file_name = entry.file_name();
+            let Some(name) = file_name
+                .to_str()
+                .and_then(|f| f.strip_prefix(&prefix))
+                .and_then(|f| f.strip_suffix(std::env::consts::EXE_SUFFIX))
+            else {
+                continue;
+            };
+            if name.is_empty()
+                || known
+                    .iter()
+                    .any(|k| k.name == name || k.aliases.iter().any(|a| a == name))
+                || found.iter().any(|f| f.name == name)
+                || !is_executable(&entry.path())
+            {
+                continue;
+            }
+            found.push(CommandDef::new(name, "").with_external(true));
+        }
+    }
+    found.sort_by(|a, b| a.name.cmp(&b.name));
+    found
+}
+
+/// The subcommands of `app`, followed by its plugins when it runs external subcommands.
+///
+/// Searching `PATH` is left to help, completion and man pages; parsing and
+/// dispatch only look at `app.subcommands()`.
+pub fn with_plugins<A: App + ?Sized>(app: &A) -> Vec<CommandDef> {
+    let mut subs = app.subcommands();
+    if app.external_subcommands() {
+        let plugins = discover(app.name(), &subs);
+        subs.extend(plugins);
+    }
+    subs
+}
+
+/// Run the executable `<app>-<name>` for the subcommand named by the first
+/// positional argument, passing it the remaining arguments.
+///
+/// Flags of `app` with an environment variable are exported to it with
+/// their resolved values, unless they only hold their default. A non-zero
+/// exit status becomes `KoralError::ExternalCommandFailed`.
+#[doc(hidden)]
+pub fn run<A: App + ?Sized>(app: &A, ctx: &Context) -> KoralResult<()> {
+    let cmd = ExternalCommand::from_context(ctx)?;
+    let program = find(app.name(), &cmd.name).ok_or_else(|| {
+        KoralError::InvalidSubcommand(format!("No subcommand or plugin named '{}'", cmd.name))
+    })?;
+
+    let mut flags = app.flags();
+    if let Some(prefix) = &ctx.inherited.env_prefix {
+        crate::flag::apply_env_prefix(&mut flags, prefix);
+    }
+    flags.extend(ctx.inherited.globals.iter().cloned());
+
+    let mut command = std::process::Command::new(&program);
+    crate::host::configure(&mut command);
+    command.args(&cmd.args);
+    for flag in &flags {
+        let (Some(env), Some(source)) = (&flag.env, ctx.sources.get(&flag.name)) else {
+            continue;
+        };
+        if *source == ValueSource::Default {
+            continue;
+        }
+        let value = match ctx.flags.get(&flag.name) {
+            Some(Some(_)) => ctx.values_of(&flag.name).join(","),
+            Some(None) => "true".to_string(),
+            None => continue,
+        };
+        command.env(env, value);
+    }
+
+    let status = command
+        .status()
+        .map_err(|e| KoralError::IoError(format!("Failed to run {}: {}", program.display(), e)))?;
+    match status.code() {
+        Some(0) => Ok(()),
+        // Terminated by a signal
+        None => Err(KoralError::ExternalCommandFailed(cmd.name, 1)),
+        Some(code) => Err(KoralError::ExternalCommandFailed(cmd.name, code)),
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
@@ -1,4 +1,5 @@
-use crate::{Context, Flag, KoralResult};
+use crate::provider::ValueSource;
+use crate::{Arg, Arity, Context, Flag, KoralResult};
 use std::ops::Deref;
 
 /// Trait for extracting data from the context.
@@ -35,33 +36,114 @@ impl<F: Flag> Deref for FlagVal<F> {
     }
 }
 
-impl<'a, F: Flag> FromContext<'a> for FlagVal<F>
-where
-    <F::Value as std::str::FromStr>::Err: std::fmt::Display,
-{
+impl<'a, F: Flag> FromContext<'a> for FlagVal<F> {
     fn from_context(ctx: &'a Context) -> KoralResult<Self> {
         match ctx.get::<F>() {
             Some(v) => Ok(FlagVal(v)),
-            None => {
-                println!(
-                    "DEBUG: Flag {} not found in context. Checking default...",
+            None => match F::default_value() {
+                Some(v) => Ok(FlagVal(v)),
+                None => Err(crate::KoralError::MissingArgument(format!(
+                    "Flag '{}' not found",
                     F::name()
-                );
-                match F::default_value() {
-                    Some(v) => {
-                        println!("DEBUG: Found default value");
-                        Ok(FlagVal(v))
-                    }
-                    None => {
-                        println!("DEBUG: No default value for {}", F::name());
-                        Err(crate::KoralError::MissingArgument(format!(
-                            "Flag '{}' not found",
-                            F::name()
-                        )))
-                    }
-                }
+                ))),
+            },
+        }
+    }
+}
+
+/// Extractor for a flag parsed by a parent command.
+///
+/// Looks through the dispatching commands, the nearest first, and falls back
+/// to the flag's default value when none of them was given the flag.
+pub struct ParentFlag<F: Flag>(pub F::Value);
+
+impl<F: Flag> Deref for ParentFlag<F> {
+    type Target = F::Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, F: Flag> FromContext<'a> for ParentFlag<F> {
+    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
+        let mut parent = ctx.parent();
+        while let Some(p) = parent {
+            if p.is_present(F::name()) {
+                return p.get::<F>().map(ParentFlag).ok_or_else(|| {
+                    crate::KoralError::FlagValueParseError(format!(
+                        "Invalid value for flag '{}'",
+                        F::name()
+                    ))
+                });
             }
+            parent = p.parent();
         }
+        F::default_value().map(ParentFlag).ok_or_else(|| {
+            crate::KoralError::MissingArgument(format!(
+                "Flag '{}' not found in parent commands",
+                F::name()
+            ))
+        })
+    }
+}
+
+/// Extractor pairing a value with where it came from.
+///
+/// `Sourced<FlagArg<F>>` yields the flag value along with its `ValueSource`.
+pub struct Sourced<T> {
+    /// The extracted value
+    pub value: T,
+    /// Where the value came from
+    pub source: ValueSource,
+}
+
+impl<T> Deref for Sourced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'a, F: Flag> FromContext<'a> for Sourced<FlagVal<F>> {
+    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
+        let value = FlagVal::<F>::from_context(ctx)?;
+        // Values not recorded by the parser come from the flag's default
+        let source = ctx.source_of(F::name()).unwrap_or(ValueSource::Default);
+        Ok(Sourced { value, source })
+    }
+}
+
+/// Extractor for typed positional arguments.
+///
+/// Use `Option<ArgVal<A>>` for arguments that may be absent.
+pub struct ArgVal<A: Arg>(pub A::Value);
+
+impl<A: Arg> Deref for ArgVal<A> {
+    type Target = A::Value;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, A: Arg> FromContext<'a> for ArgVal<A> {
+    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
+        let values = ctx.arg_values_of(A::name());
+        if values.is_empty() && A::arity() != Arity::Many {
+            return Err(crate::KoralError::MissingArgument(format!(
+                "Argument '{}' not found",
+                A::name()
+            )));
+        }
+        A::from_values(&values).map(ArgVal).map_err(|e| {
+            crate::KoralError::FlagValueParseError(format!(
+                "Invalid value for argument '{}': {}",
+                A::name(),
+                e
+            ))
+        })
     }
 }
 
@@ -103,10 +185,34 @@ where
     }
 }
 
+/// Extractor for extensions.
+pub struct Extension<T>(pub T);
+
+impl<T> Deref for Extension<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a, T: Clone + Send + Sync + 'static> FromContext<'a> for Extension<T> {
+    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
+        match ctx.get_extension::<T>() {
+            Some(v) => Ok(Extension(v.clone())),
+            None => Err(crate::KoralError::MissingArgument(format!(
+                "Extension of type '{}' not found",
+                std::any::type_name::<T>()
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::flag::Flag;
+    use crate::traits::FlagValue;
 
     struct TestFlag;
     impl Flag for TestFlag {
@@ -114,6 +220,9 @@ mod tests {
         fn name() -> &'static str {
             "test"
         }
+        fn from_values(values: &[&str]) -> Result<String, String> {
+            String::from_last(values)
+        }
         fn short() -> Option<char> {
             None
         }
@@ -145,26 +254,3 @@ mod tests {
         assert!(res.is_none());
     }
 }
-
-/// Extractor for extensions.
-pub struct Extension<T>(pub T);
-
-impl<T> Deref for Extension<T> {
-    type Target = T;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl<'a, T: Clone + Send + Sync + 'static> FromContext<'a> for Extension<T> {
-    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
-        match ctx.get_extension::<T>() {
-            Some(v) => Ok(Extension(v.clone())),
-            None => Err(crate::KoralError::MissingArgument(format!(
-                "Extension of type '{}' not found",
-                std::any::type_name::<T>()
-            ))),
-        }
-    }
-}
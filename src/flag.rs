@@ -1,8 +1,9 @@
-use crate::traits::FlagValue;
-
 /// Validator function signature
 pub type Validator = fn(&str) -> Result<(), String>;
 
+/// Conversion check run on the values given for a flag
+pub type FlagCheck = fn(&[&str]) -> Result<(), String>;
+
 /// Internal representation of a flag used by Parser and App.
 #[derive(Clone, Debug)]
 pub struct FlagDef {
@@ -30,6 +31,36 @@ pub struct FlagDef {
     pub value_name: Option<String>,
     /// Help section heading
     pub help_heading: Option<String>,
+    /// Whether the flag can be repeated, collecting every value
+    pub multiple: bool,
+    /// Delimiter splitting a single occurrence into several values
+    pub value_delimiter: Option<char>,
+    /// Whether the flag counts its occurrences (e.g. `-vvv` is 3)
+    pub count: bool,
+    /// Whether a boolean flag also accepts `--no-<name>` and `--<name>=true|false`
+    pub negatable: bool,
+    /// Value used when the flag is given without one (`--color` vs `--color=always`)
+    pub missing_value: Option<String>,
+    /// Flags that cannot be used together with this one
+    pub conflicts_with: Vec<String>,
+    /// Flags that must also be present when this one is used
+    pub requires: Vec<String>,
+    /// Conditions `(flag, value)` under which this flag becomes required
+    pub required_if_eq: Vec<(String, String)>,
+    /// Flags whose absence makes this flag required
+    pub required_unless_present: Vec<String>,
+    /// Defaults `(flag, value, default)` applied when another flag has a given value
+    pub default_value_if: Vec<(String, String, String)>,
+    /// Accepted values; empty when any value is allowed
+    pub possible_values: Vec<String>,
+    /// Whether `@path` and `-` (stdin) values are replaced by the content read
+    pub from_file: bool,
+    /// Whether the value is masked in debug and provenance output
+    pub sensitive: bool,
+    /// Whether subcommands at any depth accept the flag too
+    pub global: bool,
+    /// Checks that the values convert to the flag's type
+    pub check: Option<FlagCheck>,
 }
 
 impl FlagDef {
@@ -41,25 +72,89 @@ impl FlagDef {
             long: F::long().map(|s| s.to_string()),
             help: F::help().to_string(),
             takes_value: F::takes_value(),
-            default_value: F::default_value().map(|v| v.to_string()),
+            default_value: F::default_str().map(|v| v.to_string()),
             env: F::env().map(|s| s.to_string()),
             validator: F::validator(),
             aliases: F::aliases().into_iter().map(|s| s.to_string()).collect(),
             required: F::required(),
             value_name: F::value_name().map(|s| s.to_string()),
             help_heading: F::help_heading().map(|s| s.to_string()),
+            multiple: F::multiple(),
+            value_delimiter: F::value_delimiter(),
+            count: F::count(),
+            negatable: F::negatable(),
+            missing_value: F::missing_value().map(|s| s.to_string()),
+            conflicts_with: F::conflicts_with()
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            requires: F::requires().into_iter().map(|s| s.to_string()).collect(),
+            required_if_eq: F::required_if_eq()
+                .into_iter()
+                .map(|(f, v)| (f.to_string(), v.to_string()))
+                .collect(),
+            required_unless_present: F::required_unless_present()
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            default_value_if: F::default_value_if()
+                .into_iter()
+                .map(|(f, v, d)| (f.to_string(), v.to_string(), d.to_string()))
+                .collect(),
+            possible_values: F::possible_values()
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect(),
+            from_file: F::from_file(),
+            sensitive: F::sensitive(),
+            global: F::global(),
+            check: Some(check_values::<F>),
+        }
+    }
+
+    /// Split a raw occurrence into its values, honouring `value_delimiter`.
+    pub fn split_value(&self, raw: &str) -> Vec<String> {
+        split_raw(raw, self.multiple, self.value_delimiter)
+    }
+}
+
+/// Built-in flags that never read an environment variable.
+const NO_ENV_FLAGS: &[&str] = &["version", "config"];
+
+/// Append a name to an environment variable prefix, e.g. `CLOUD_CLI` + `deploy`
+/// gives `CLOUD_CLI_DEPLOY`.
+pub fn scoped_env_prefix(prefix: &str, name: &str) -> String {
+    format!("{}_{}", prefix, name.to_uppercase().replace('-', "_"))
+}
+
+/// Give every flag without an explicit `env` the variable `<PREFIX>_<FLAG_NAME>`.
+pub fn apply_env_prefix(flags: &mut [FlagDef], prefix: &str) {
+    for flag in flags {
+        if flag.env.is_none() && !NO_ENV_FLAGS.contains(&flag.name.as_str()) {
+            flag.env = Some(scoped_env_prefix(prefix, &flag.name));
         }
     }
 }
 
+fn check_values<F: Flag>(values: &[&str]) -> Result<(), String> {
+    F::from_values(values).map(|_| ())
+}
+
+fn split_raw(raw: &str, multiple: bool, delimiter: Option<char>) -> Vec<String> {
+    match delimiter {
+        Some(d) if multiple => raw.split(d).map(|s| s.to_string()).collect(),
+        _ => vec![raw.to_string()],
+    }
+}
+
 // Re-defining for clarity and applying suggestion
 /// Trait defining a command-line flag.
-pub trait Flag
-where
-    <Self::Value as std::str::FromStr>::Err: std::fmt::Display,
-{
+pub trait Flag {
     /// The type of value this flag holds.
-    type Value: FlagValue;
+    ///
+    /// This is usually a [`FlagValue`](crate::traits::FlagValue), or a `Vec` of them
+    /// for flags accepting multiple values.
+    type Value: Clone + Send + Sync + 'static;
 
     /// The canonical name of the flag.
     fn name() -> &'static str;
@@ -84,9 +179,31 @@ where
         true
     }
 
+    /// Convert the raw values collected by the parser into the typed value.
+    ///
+    /// `values` holds every value given for the flag, in order.
+    /// It is empty for boolean flags, which take no value.
+    ///
+    /// There is no default because `Value` may be a `Vec`; hand-written impls
+    /// forward to [`FlagValue`](crate::traits::FlagValue), e.g.
+    /// `String::from_last(values)`, `bool::from_switch(values)` or
+    /// `u32::from_each(values)` for a `Vec<u32>`.
+    fn from_values(values: &[&str]) -> Result<Self::Value, String>;
+
+    /// Default value as it would be written on the command line.
+    fn default_str() -> Option<&'static str> {
+        None
+    }
+
     /// Default value if not provided.
+    ///
+    /// Parsed from [`default_str`](Flag::default_str), which is also what help
+    /// shows and what the parser applies, so override that instead.
     fn default_value() -> Option<Self::Value> {
-        None
+        let raw = Self::default_str()?;
+        let parts = split_raw(raw, Self::multiple(), Self::value_delimiter());
+        let parts: Vec<&str> = parts.iter().map(|s| s.as_str()).collect();
+        Self::from_values(&parts).ok()
     }
 
     /// Environment variable to populate the flag from.
@@ -118,4 +235,77 @@ where
     fn help_heading() -> Option<&'static str> {
         None
     }
+
+    /// Whether the flag can be repeated, collecting every value.
+    fn multiple() -> bool {
+        false
+    }
+
+    /// Delimiter splitting a single occurrence into several values (e.g. ',').
+    fn value_delimiter() -> Option<char> {
+        None
+    }
+
+    /// Whether the flag counts its occurrences instead of taking a value.
+    fn count() -> bool {
+        false
+    }
+
+    /// Whether the boolean flag can be switched off with `--no-<name>` or `--<name>=false`.
+    fn negatable() -> bool {
+        false
+    }
+
+    /// Value used when the flag is given without one.
+    /// Such a flag only takes a value attached with `=` (or `-cVALUE`),
+    /// so it never consumes the following argument.
+    fn missing_value() -> Option<&'static str> {
+        None
+    }
+
+    /// Names of flags that cannot be used together with this one.
+    fn conflicts_with() -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Names of flags that must also be present when this one is used.
+    fn requires() -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Conditions `(flag, value)` under which this flag becomes required.
+    fn required_if_eq() -> Vec<(&'static str, &'static str)> {
+        vec![]
+    }
+
+    /// Flags whose absence makes this flag required.
+    fn required_unless_present() -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Defaults `(flag, value, default)` used when another flag has a given value.
+    fn default_value_if() -> Vec<(&'static str, &'static str, &'static str)> {
+        vec![]
+    }
+
+    /// Accepted values, checked by the parser and offered by help and completion.
+    fn possible_values() -> Vec<&'static str> {
+        vec![]
+    }
+
+    /// Whether a command line value of `@path` or `-` is replaced by the
+    /// trimmed content of that file or of stdin.
+    fn from_file() -> bool {
+        false
+    }
+
+    /// Whether the value is a secret to mask in debug and provenance output.
+    fn sensitive() -> bool {
+        false
+    }
+
+    /// Whether the flag is accepted by every subcommand below the command declaring it.
+    fn global() -> bool {
+        false
+    }
 }
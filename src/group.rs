@@ -0,0 +1,57 @@
+/// Internal representation of a flag group used by Parser and App.
+#[derive(Clone, Debug)]
+pub struct GroupDef {
+    /// Name of the group
+    pub name: String,
+    /// Names of the member flags
+    pub flags: Vec<String>,
+    /// Whether at least one member must be present
+    pub required: bool,
+    /// Whether several members may be used together
+    pub multiple: bool,
+}
+
+impl GroupDef {
+    /// Create a GroupDef from a FlagGroup trait implementation
+    pub fn from_trait<G: FlagGroup>() -> Self {
+        Self {
+            name: G::name().to_string(),
+            flags: G::flags().into_iter().map(|s| s.to_string()).collect(),
+            required: G::required(),
+            multiple: G::multiple(),
+        }
+    }
+
+    /// Whether the named flag belongs to this group.
+    pub fn contains(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    /// Members rendered for error messages, e.g. `'--id', '--name'`.
+    pub(crate) fn describe(&self) -> String {
+        self.flags
+            .iter()
+            .map(|f| format!("'--{}'", f))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Trait defining a group of related flags.
+pub trait FlagGroup {
+    /// The name of the group.
+    fn name() -> &'static str;
+
+    /// Names of the member flags.
+    fn flags() -> Vec<&'static str>;
+
+    /// Whether at least one member must be present.
+    fn required() -> bool {
+        false
+    }
+
+    /// Whether several members may be used together.
+    fn multiple() -> bool {
+        true
+    }
+}
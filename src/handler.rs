@@ -39,9 +39,15 @@ where
         // Easier to manually construct.
         let typed_ctx = Context {
             flags: ctx.flags,
+            values: ctx.values,
+            sources: ctx.sources,
+            sensitive: ctx.sensitive,
             args: ctx.args,
+            arg_values: ctx.arg_values,
             state: ctx.state,
             extensions: ctx.extensions,
+            inherited: ctx.inherited,
+            parent: ctx.parent,
             app: Some(app),
         };
         (self)(typed_ctx)
@@ -143,14 +149,16 @@ macro_rules! impl_handler {
                 // We can construct a combined context.
 
                 #[allow(unused_variables)]
-                let ctx_with_app = ctx.with_app(app);
+                let mut ctx_with_app = ctx.with_app(app);
 
                 // Now extract
                 $(
                     let $ty = $ty::from_context(&ctx_with_app)?;
                 )*
 
-                (self)($($ty,)*)
+                let result = (self)($($ty,)*);
+                crate::host::keep_extensions(&mut ctx_with_app.extensions);
+                result
             }
         }
     };
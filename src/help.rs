@@ -2,6 +2,21 @@ use crate::traits::App;
 
 /// Generate help message for the application with ANSI colors.
 pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
+    let mut flags = app.flags();
+    if let Some(prefix) = app.env_prefix() {
+        crate::flag::apply_env_prefix(&mut flags, prefix);
+    }
+    generate_help_with_flags(app, flags, &[], crate::output::default_width())
+}
+
+/// Generate help message listing `all_flags` instead of the app's own flags,
+/// along with the user's command aliases, wrapped to `term_width` columns.
+pub(crate) fn generate_help_with_flags<T: App + ?Sized>(
+    app: &T,
+    all_flags: Vec<crate::flag::FlagDef>,
+    aliases: &[crate::alias::UserAlias],
+    term_width: usize,
+) -> String {
     let mut out = String::new();
     let header_style = anstyle::Style::new().bold().underline();
     let literal_style = anstyle::Style::new()
@@ -11,16 +26,44 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
         .bold()
         .fg_color(Some(anstyle::Color::Ansi(anstyle::AnsiColor::Yellow)));
 
+    let args = crate::arg::ordered(&app.args());
+    let mut usage_tail = String::from(" [options]");
+    for group in app.groups() {
+        // Groups allowing several members are covered by [options]
+        if !group.required && group.multiple {
+            continue;
+        }
+        let members: Vec<String> = group
+            .flags
+            .iter()
+            .filter_map(|name| all_flags.iter().find(|f| &f.name == name))
+            .map(|flag| {
+                if flag.takes_value {
+                    let vname = flag.value_name.as_deref().unwrap_or("value");
+                    format!("--{} <{}>", flag.name, vname)
+                } else {
+                    format!("--{}", flag.name)
+                }
+            })
+            .collect();
+        if group.required {
+            usage_tail.push_str(&format!(" <{}>", members.join("|")));
+        } else {
+            usage_tail.push_str(&format!(" [{}]", members.join("|")));
+        }
+    }
+    for arg in &args {
+        usage_tail.push(' ');
+        usage_tail.push_str(&arg.placeholder());
+    }
+    if args.is_empty() || !app.subcommands().is_empty() {
+        usage_tail.push_str(" [command]");
+    }
     out.push_str(&format!(
-        "{header_style}Usage:{header_style:#} {title_style}{}{title_style:#} [options] [command]\n",
-        app.name()
+        "{header_style}Usage:{header_style:#} {title_style}{}{title_style:#}{}\n",
+        app.name(),
+        usage_tail
     ));
-    // Get terminal width
-    let term_width = std::env::var("COLUMNS")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(100);
-
     // Helper to wrap text
     let wrap_desc = |text: &str, indent_len: usize| -> String {
         // If indent is too large relative to term_width, just print simple
@@ -59,7 +102,7 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
     let desc = app.description();
     if !desc.is_empty() {
         // Wrap description with no indent
-        let wrapped = wrap_desc(&desc, 0);
+        let wrapped = wrap_desc(desc, 0);
         out.push_str(&format!("{}\n", wrapped));
     }
 
@@ -72,7 +115,7 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
     use std::collections::BTreeMap;
     let mut groups: BTreeMap<Option<String>, Vec<HelpItem>> = BTreeMap::new();
 
-    let mut flags = app.flags();
+    let mut flags = all_flags.clone();
     // Sort flags by name
     flags.sort_by(|a, b| a.name.cmp(&b.name));
 
@@ -115,10 +158,26 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
             name_part_len += 4; // ", -s"
         }
 
-        if flag.takes_value {
+        if flag.negatable {
+            name_part_display.push_str(&format!(
+                ", {literal_style}--no-{}{literal_style:#}",
+                flag.name
+            ));
+            name_part_len += 7 + flag.name.len(); // ", --no-" + name
+        }
+
+        if flag.takes_value && flag.missing_value.is_some() {
+            let vname = flag.value_name.as_deref().unwrap_or("value");
+            name_part_display.push_str(&format!(" [=<{}>]", vname));
+            name_part_len += 6 + vname.len(); // " [=<vname>]"
+        } else if flag.takes_value {
             let vname = flag.value_name.as_deref().unwrap_or("value");
             name_part_display.push_str(&format!(" <{}>", vname));
             name_part_len += 3 + vname.len(); // " <vname>"
+            if flag.multiple {
+                name_part_display.push_str("...");
+                name_part_len += 3;
+            }
         }
 
         if !flag.aliases.is_empty() {
@@ -147,14 +206,32 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
             name_part_len += 11 + aliases_len + joins + 1;
         }
 
-        groups
-            .entry(flag.help_heading.clone())
-            .or_default()
-            .push(HelpItem {
-                display: name_part_display,
-                real_len: name_part_len,
-                desc: flag.help.clone(),
-            });
+        let mut desc = flag.help.clone();
+        if flag.takes_value && !flag.possible_values.is_empty() {
+            if !desc.is_empty() {
+                desc.push(' ');
+            }
+            desc.push_str(&format!(
+                "[possible values: {}]",
+                flag.possible_values.join(", ")
+            ));
+        }
+        if let Some(env) = &flag.env {
+            if !desc.is_empty() {
+                desc.push(' ');
+            }
+            desc.push_str(&format!("[env: {}]", env));
+        }
+
+        let heading = flag
+            .help_heading
+            .clone()
+            .or_else(|| flag.global.then(|| "Global Options".to_string()));
+        groups.entry(heading).or_default().push(HelpItem {
+            display: name_part_display,
+            real_len: name_part_len,
+            desc,
+        });
     }
 
     // Sort items within groups (insertion order mostly respected, but flags were sorted by name)
@@ -172,6 +249,21 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
     let padding = 4;
     let indent = max_width + 2 + padding; // "  " + name + pad
 
+    if !args.is_empty() {
+        out.push_str("\nArguments:\n");
+        let placeholders: Vec<String> = args.iter().map(|a| a.placeholder()).collect();
+        let max_arg_width = placeholders.iter().map(|p| p.len()).max().unwrap_or(0);
+        let arg_indent = max_arg_width + 2 + padding;
+        for (arg, placeholder) in args.iter().zip(&placeholders) {
+            let pad = " ".repeat(max_arg_width - placeholder.len() + padding);
+            let desc = wrap_desc(&arg.help, arg_indent);
+            out.push_str(&format!(
+                "  {literal_style}{}{literal_style:#}{}{}\n",
+                placeholder, pad, desc
+            ));
+        }
+    }
+
     // Output groups
     // 1. None (Options) first
     if let Some(items) = groups.get(&None) {
@@ -197,10 +289,15 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
         }
     }
 
-    let mut subs = app.subcommands();
-    if !subs.is_empty() {
-        out.push_str("\nCommands:\n");
-        subs.sort_by(|a, b| a.name.cmp(&b.name));
+    let (external, mut builtin): (Vec<_>, Vec<_>) = crate::external::with_plugins(app)
+        .into_iter()
+        .partition(|s| s.external);
+    builtin.sort_by(|a, b| a.name.cmp(&b.name));
+    for (heading, subs) in [("Commands", builtin), ("External Commands", external)] {
+        if subs.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n{}:\n", heading));
 
         let max_sub_width = subs.iter().map(|s| s.name.len()).max().unwrap_or(0);
         let sub_indent = max_sub_width + 2 + padding;
@@ -220,5 +317,19 @@ pub fn generate_help<T: App + ?Sized>(app: &T) -> String {
         }
     }
 
+    if !aliases.is_empty() {
+        out.push_str("\nAliases:\n");
+        let max_alias_width = aliases.iter().map(|a| a.name.len()).max().unwrap_or(0);
+        let alias_indent = max_alias_width + 2 + padding;
+        for alias in aliases {
+            let pad = " ".repeat(max_alias_width - alias.name.len() + padding);
+            let desc = wrap_desc(&alias.expansion.join(" "), alias_indent);
+            out.push_str(&format!(
+                "  {literal_style}{}{literal_style:#}{}{}\n",
+                alias.name, pad, desc
+            ));
+        }
+    }
+
     out
 }
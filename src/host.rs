@@ -0,0 +1,111 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io::Read;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Stand-ins for the process's environment, working directory and stdin.
+#[derive(Default)]
+pub(crate) struct Fake {
+    /// The only environment variables visible
+    pub vars: HashMap<String, String>,
+    /// Working directory relative paths are resolved against
+    pub cwd: Option<PathBuf>,
+    /// Input not read yet
+    pub stdin: Rc<RefCell<String>>,
+    /// Extensions left in the context of the handler that ran
+    pub extensions: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+thread_local! {
+    static FAKE: RefCell<Option<Fake>> = const { RefCell::new(None) };
+}
+
+/// Clears the fake even when the closure panics.
+struct Reset;
+
+impl Drop for Reset {
+    fn drop(&mut self) {
+        let _ = FAKE.try_with(|fake| fake.borrow_mut().take());
+    }
+}
+
+/// Run `f` with `fake` standing in for the process on this thread.
+pub(crate) fn with_fake<R>(fake: Fake, f: impl FnOnce() -> R) -> (R, Fake) {
+    FAKE.with(|cell| *cell.borrow_mut() = Some(fake));
+    let reset = Reset;
+    let result = f();
+    let fake = FAKE
+        .with(|cell| cell.borrow_mut().take())
+        .unwrap_or_default();
+    drop(reset);
+    (result, fake)
+}
+
+fn faked<R>(f: impl FnOnce(&mut Fake) -> R) -> Option<R> {
+    FAKE.with(|cell| cell.borrow_mut().as_mut().map(f))
+}
+
+/// Value of an environment variable.
+pub(crate) fn var(name: &str) -> Option<String> {
+    faked(|fake| fake.vars.get(name).cloned()).unwrap_or_else(|| std::env::var(name).ok())
+}
+
+/// Value of an environment variable, which may not be Unicode.
+pub(crate) fn var_os(name: &str) -> Option<OsString> {
+    faked(|fake| fake.vars.get(name).map(OsString::from)).unwrap_or_else(|| std::env::var_os(name))
+}
+
+/// The current working directory.
+pub(crate) fn current_dir() -> Option<PathBuf> {
+    faked(|fake| fake.cwd.clone())
+        .flatten()
+        .or_else(|| std::env::current_dir().ok())
+}
+
+/// Make a relative path relative to the faked working directory, if any.
+pub(crate) fn resolve(path: impl Into<PathBuf>) -> PathBuf {
+    let path = path.into();
+    match faked(|fake| fake.cwd.clone()).flatten() {
+        Some(cwd) if path.is_relative() => cwd.join(path),
+        _ => path,
+    }
+}
+
+/// Everything left on stdin.
+pub(crate) fn read_stdin() -> std::io::Result<String> {
+    if let Some(stdin) = faked(|fake| fake.stdin.clone()) {
+        return Ok(std::mem::take(&mut *stdin.borrow_mut()));
+    }
+    let mut content = String::new();
+    std::io::stdin().read_to_string(&mut content)?;
+    Ok(content)
+}
+
+/// Give a child process the faked environment and working directory.
+pub(crate) fn configure(command: &mut std::process::Command) {
+    faked(|fake| {
+        command.env_clear().envs(&fake.vars);
+        if let Some(cwd) = &fake.cwd {
+            command.current_dir(cwd);
+        }
+    });
+}
+
+/// Keep the extensions of a finished handler's context, for the fake's owner to see.
+pub(crate) fn keep_extensions(extensions: &mut HashMap<TypeId, Box<dyn Any + Send + Sync>>) {
+    if extensions.is_empty() {
+        return;
+    }
+    let _ = FAKE.try_with(|cell| {
+        if let Ok(mut fake) = cell.try_borrow_mut() {
+            if let Some(fake) = fake.as_mut() {
+                for (id, value) in extensions.drain() {
+                    fake.extensions.entry(id).or_insert(value);
+                }
+            }
+        }
+    });
+}
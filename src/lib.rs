@@ -18,7 +18,7 @@
 //!
 //! ## Key Features
 //!
-//! - **Declarative Macros**: Use `#[derive(App)]`, `#[derive(Subcommand)]`, and `#[derive(Flag)]`.
+//! - **Declarative Macros**: Use `#[derive(App)]`, `#[derive(Subcommand)]`, `#[derive(Flag)]`, and `#[derive(Arg)]`.
 //! - **Dependency Injection**: Defines handlers that extract States and Flags directly (`fn run(state: State<S>, verbose: FlagVal<V>)`).
 //! - **Middleware**: Hook into lifecycle execution (`before`/`after`) for logging, auth, etc. Supports both static registration and dynamic injection.
 //! - **Type-Safe**: Flags are strongly typed. Custom types (Enums/Structs) supported via `#[derive(FlagValue)]`.
@@ -137,8 +137,30 @@
 //!
 //! - **required**: Marks the flag as mandatory (`required = true`).
 //! - **env**: Sets an environment variable to read from if the flag is missing (`env = "MY_ENV_VAR"`).
+//!   When it is unset, `MY_ENV_VAR_FILE` may name a file holding the value.
+//! - **from_file**: Replaces a `@path` value with the trimmed content of that file, and `-` with stdin.
+//! - **sensitive**: Masks the value in `Context`'s `Debug` output and in `ctx.describe(...)`.
+//! - **global**: Accepts the flag in every subcommand below the command declaring it;
+//!   its value reaches their `Context` wherever it was given on the command line.
 //! - **value_name**: Customizes the placeholder name in help/completion (e.g. `value_name = "FILE"` -> `--config <FILE>`).
 //! - **help_heading**: Groups the flag under a custom heading in the help message.
+//! - **Multiple values**: A `Vec<T>` flag collects every occurrence (`--tag a --tag b`).
+//!   Add `value_delimiter = ','` to also split `--tag a,b`.
+//! - **count**: Counts occurrences instead of taking a value (`-vvv` gives `3` for a `u8` flag).
+//! - **negatable**: Lets a `bool` flag be switched off with `--no-<name>` or `--<name>=false`,
+//!   overriding its environment variable and default.
+//! - **missing_value**: Makes the value optional (`--color` uses `missing_value = "always"`,
+//!   `--color=never` sets it); the following argument is never consumed.
+//! - **possible_values**: Restricts a flag to a set of values (`possible_values = "json,text"`).
+//!   Enums deriving `FlagValue` provide theirs automatically.
+//! - **conflicts_with** / **requires**: Comma-separated flag names that cannot be combined with,
+//!   or must accompany, this flag (`conflicts_with = "yaml"`).
+//! - **Conditional requirements**: `required_if_eq("mode", "remote")` and
+//!   `required_unless_present("config")` make a flag mandatory depending on other flags.
+//! - **default_value_if**: `default_value_if("env", "prod", "3")` picks a default when another
+//!   flag has a given value.
+//! - **Groups**: `#[app(group(name = "target", flags(IdFlag, NameFlag), required, multiple = false))]`
+//!   (or `#[derive(FlagGroup)]` with `#[app(groups(...))]`) requires exactly one of several flags.
 //! - **Strict Mode**: Add `#[app(strict)]` to treat unknown flags as errors instead of positional args.
 //!
 //! ```rust
@@ -167,6 +189,258 @@
 //! struct SecureApp;
 //! ```
 //!
+//! ### Positional Arguments
+//!
+//! Declare typed positional arguments with `#[derive(Arg)]` and extract them with `ArgVal`.
+//! A `Vec<T>` argument takes every remaining value; `arity = "optional"` makes one optional.
+//!
+//! ```rust
+//! # use koral::prelude::*;
+//! #[derive(Arg)]
+//! #[arg(name = "src", value_name = "FILE", help = "Files to copy")]
+//! struct Src(Vec<String>);
+//!
+//! #[derive(Arg)]
+//! #[arg(name = "dst", help = "Destination directory")]
+//! struct Dst(String);
+//!
+//! #[derive(Default, App)]
+//! #[app(name = "cp", action = run)]
+//! #[app(args(Src, Dst))]
+//! struct CopyApp;
+//!
+//! fn run(src: ArgVal<Src>, dst: ArgVal<Dst>) -> KoralResult<()> {
+//!     println!("Copying {:?} to {}", *src, *dst);
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Configuration Files
+//!
+//! `#[app(config_file)]` reads flag values from a TOML, JSON or INI file chosen by
+//! `--config <FILE>`, the `<NAME>_CONFIG` environment variable, or
+//! `$XDG_CONFIG_HOME/<name>/config.{toml,json,ini}`. Values are resolved in the order
+//! command line > environment > config file > default, and a `[deploy]` section
+//! applies to the `deploy` subcommand.
+//!
+//! ```rust
+//! # use koral::prelude::*;
+//! #[derive(Flag)]
+//! #[flag(name = "region", default = "us-east-1")]
+//! struct RegionFlag(String);
+//!
+//! #[derive(Default, App)]
+//! #[app(name = "deployer", config_file)]
+//! #[app(flags(RegionFlag))]
+//! struct Deployer;
+//! ```
+//!
+//! An `[alias]` table defines command aliases for the user: with
+//! `lsc = "k8s clusters list --output json"`, `deployer lsc` runs that command line.
+//! Aliases may use other aliases, are listed in help under "Aliases", and never
+//! replace a built-in command of the same name.
+//!
+//! `#[app(dotenv)]` (or `dotenv = ".env.local"`) lets `env` lookups fall back to a
+//! dotenv file found in the current directory or its parents, or named by `KORAL_DOTENV`.
+//! Real environment variables take precedence and the process environment is not modified.
+//!
+//! `#[app(env_prefix = "CLOUD_CLI")]` gives every flag without an explicit `env` the
+//! variable `CLOUD_CLI_<FLAG>` (`CLOUD_CLI_<SUBCMD>_<FLAG>` in subcommands). Help shows
+//! it as `[env: ...]` and the man page lists it under ENVIRONMENT.
+//!
+//! `#[app(response_files)]` expands `@args.txt` into the shell-quoted arguments listed in
+//! that file (one per line is typical) before anything else, so it may even hold `--help`.
+//! Write `@@value` for a literal `@value`, e.g. to pass `@path` to a `from_file` flag.
+//!
+//! ### Value Providers
+//!
+//! `#[app(providers(...))]` replaces the sources consulted for missing flags, tried in
+//! the listed order. Include `EnvProvider` to keep environment variables; a
+//! `#[app(provider)]` field adds a configured instance. Subcommands without
+//! `providers(...)` of their own use their parent's.
+//!
+//! The rest of the lookup order is fixed: the command line comes first, and the
+//! dotenv file, the config file, then flag defaults come after the providers.
+//!
+//! ```rust
+//! # use koral::prelude::*;
+//! use koral::provider::{EnvProvider, ValueProvider};
+//!
+//! #[derive(Default)]
+//! struct Vault;
+//!
+//! impl ValueProvider for Vault {
+//!     fn get_value(&self, flag: &FlagDef) -> Option<String> {
+//!         (flag.name == "token").then(|| "s3cr3t".to_string())
+//!     }
+//! }
+//!
+//! #[derive(Default, App)]
+//! #[app(name = "vaulted", providers(EnvProvider, Vault))]
+//! struct VaultedApp;
+//! ```
+//!
+//! `ctx.source_of("token")` reports where a value came from as a `ValueSource`
+//! (command line, environment, config file, provider or default), and the
+//! `Sourced<FlagArg<F>>` extractor pairs a flag value with its source.
+//!
+//! ### Parent Commands
+//!
+//! A subcommand's `Context` keeps the context of the command that dispatched to it,
+//! reachable with `ctx.parent()`. Extensions inserted by a parent's middleware are
+//! found by `Extension<T>`, and `ParentFlag<F>` extracts a flag parsed by a parent.
+//!
+//! ### External Subcommands
+//!
+//! `#[app(external_subcommands)]` runs an unrecognised subcommand `foo` as the
+//! executable `<app>-foo` found on `PATH`, with the remaining arguments untouched and
+//! flags having an environment variable exported with their values. Help lists the
+//! discovered executables under "External Commands" and completion offers them.
+//! `#[app(external_subcommands = handler)]` calls a handler receiving `ExternalCommand`
+//! instead. Apps with positional arguments cannot take external subcommands.
+//!
+//! ```rust
+//! # use koral::prelude::*;
+//! use koral::external::ExternalCommand;
+//!
+//! fn plugin(cmd: ExternalCommand) -> KoralResult<()> {
+//!     println!("no plugin called {} (args: {:?})", cmd.name, cmd.args);
+//!     Ok(())
+//! }
+//!
+//! #[derive(Default, App)]
+//! #[app(name = "ops", external_subcommands = plugin)]
+//! struct OpsApp;
+//! ```
+//!
+//! ### Interactive Sessions
+//!
+//! `app.repl()` reads command lines from the terminal and runs each one as if it
+//! were passed to `run`, so `repl_with_state` keeps state between commands. Lines use
+//! shell quoting and `help [command]` and `exit` are built in. `#[app(repl)]` adds a
+//! `shell` subcommand starting a session; use `koral::repl::Repl` to change the prompt
+//! or input.
+//!
+//! Line editing, history and Tab completion of commands and flags need the `repl`
+//! feature, which is off by default (`features = ["repl"]`). Without it,
+//! sessions, including the `shell` subcommand, read plain lines from the terminal and
+//! keep no history.
+//!
+//! ```rust,no_run
+//! # use koral::prelude::*;
+//! #[derive(Default, App)]
+//! #[app(name = "kv", repl)]
+//! struct KvApp;
+//!
+//! fn main() -> KoralResult<()> {
+//!     let mut store = std::collections::HashMap::<String, String>::new();
+//!     KvApp.repl_with_state(&mut store)
+//! }
+//! ```
+//!
+//! ### Output
+//!
+//! Help, version, prompts and REPL errors go through the `Terminal` of the running
+//! command, which is the process's stdio unless replaced. Handlers write to it through
+//! `ctx.terminal()` or the `Terminal` extractor. `koral::output::run_with_terminal` runs
+//! an app against other writers, such as `Buffer`s capturing everything, with its own
+//! color setting and wrapping width.
+//!
+//! ```rust
+//! # use koral::prelude::*;
+//! use koral::output::{run_with_terminal, Buffer};
+//! use std::io::Write;
+//!
+//! fn hello(term: Terminal) -> KoralResult<()> {
+//!     writeln!(term.stdout(), "Hello!")?;
+//!     Ok(())
+//! }
+//!
+//! #[derive(Default, App)]
+//! #[app(name = "hello", action = hello)]
+//! struct HelloApp;
+//!
+//! let out = Buffer::new();
+//! let term = Terminal::new(out.clone(), Buffer::new());
+//! run_with_terminal(&mut HelloApp, term, None, vec!["hello".into()]).unwrap();
+//! assert_eq!(out.contents(), "Hello!\n");
+//! ```
+//!
+//! ### Prompting for Missing Flags
+//!
+//! With `#[app(prompt_missing)]`, a required flag missing when run from a terminal is
+//! asked for instead of failing: booleans get a `y/N` question, flags with possible
+//! values a numbered menu and `sensitive` flags hidden input. Optional flags are never
+//! asked for. An answer that does not convert to the flag's type or fails its validator
+//! is rejected and the flag asked for again. `--no-input`, or input that is not a
+//! terminal, keeps the usual missing-flag error. Tests can answer with their own
+//! `PromptIo` through `koral::prompt::run_with_io`.
+//!
+//! ### Confirming Destructive Commands
+//!
+//! `#[app(confirm = "Delete cluster {name}?")]` asks before the handler runs, with
+//! `{name}` replaced by the value of that flag or argument. The generated `--yes`/`-y`
+//! flag, or `<PREFIX>_ASSUME_YES` (the root's `env_prefix`, else its name), answers for
+//! the user; without a terminal the command is refused unless `--yes` is given. Handlers
+//! can ask their own questions with the `Confirm` extractor, given `#[app(confirm)]`.
+//!
+//! ```rust
+//! # use koral::prelude::*;
+//! #[derive(Arg)]
+//! #[arg(name = "name")]
+//! struct ClusterName(String);
+//!
+//! fn delete(name: ArgVal<ClusterName>) -> KoralResult<()> {
+//!     println!("Deleting {}", *name);
+//!     Ok(())
+//! }
+//!
+//! #[derive(Default, App)]
+//! #[app(name = "delete", action = delete, confirm = "Delete cluster {name}?")]
+//! #[app(args(ClusterName))]
+//! struct DeleteCmd;
+//! ```
+//!
+//! ### Testing
+//!
+//! `koral::testing::run` runs an app in-process and returns a `TestOutcome` with the
+//! captured stdout and stderr, the exit code, the result and the extensions left for
+//! the handler. A `Harness` sets environment variables, a working directory, a config
+//! directory and stdin for the run, all without touching the process's own; help text
+//! can be compared with snapshot files, written by running the tests with
+//! `KORAL_UPDATE_SNAPSHOTS=1`.
+//!
+//! ```rust
+//! # use koral::prelude::*;
+//! use koral::testing::{self, Harness};
+//!
+//! #[derive(Flag, Debug)]
+//! #[flag(name = "name", env = "GREET_NAME", default = "world")]
+//! struct NameFlag(String);
+//!
+//! fn greet(term: Terminal, name: FlagArg<NameFlag>) -> KoralResult<()> {
+//!     term.print(&format!("Hello, {}!\n", *name))
+//! }
+//!
+//! #[derive(Default, App)]
+//! #[app(name = "greet", action = greet, strict)]
+//! #[app(flags(NameFlag))]
+//! struct GreetApp;
+//!
+//! testing::run(&mut GreetApp, ["greet", "--name", "koral"])
+//!     .assert_success()
+//!     .assert_stdout_contains("Hello, koral!");
+//!
+//! let outcome = Harness::new()
+//!     .env("GREET_NAME", "env")
+//!     .run(&mut GreetApp, ["greet"]);
+//! assert_eq!(outcome.stdout, "Hello, env!\n");
+//!
+//! testing::run(&mut GreetApp, ["greet", "--nope"])
+//!     .assert_exit_code(2)
+//!     .assert_stderr_contains("Unknown flag");
+//! ```
+//!
 //! ### Custom Flag Types
 //!
 //! Easily parse Enums or Structs.
@@ -203,26 +477,45 @@
 //! }
 //! ```
 
+pub(crate) mod alias;
 pub(crate) mod app;
+pub(crate) mod arg;
 pub(crate) mod command;
 /// Shell completion generation.
 pub mod completion;
+/// Configuration file formats.
+pub mod config;
+/// Confirmation of destructive actions.
+pub mod confirm;
 pub(crate) mod context;
 pub(crate) mod error;
+/// External subcommands (`<app>-<name>` executables).
+pub mod external;
 /// Extractors for dependency injection.
 pub mod extract;
 pub(crate) mod flag;
+pub(crate) mod group;
 pub(crate) mod handler;
 /// Help message generation.
 pub mod help;
+pub(crate) mod host;
 /// Man page generation.
 pub mod man;
 /// Middlewares.
 pub mod middleware;
+/// Output of commands.
+pub mod output;
 /// Command line argument parser.
 pub(crate) mod parser;
+/// Prompts for missing values.
+pub mod prompt;
 /// Value providers.
 pub mod provider;
+/// Interactive sessions.
+pub mod repl;
+pub(crate) mod response;
+/// In-process test harness.
+pub mod testing;
 /// Core traits for the Koral framework.
 pub mod traits;
 
@@ -231,6 +524,9 @@ pub mod internal {
     pub mod app {
         pub use crate::app::*;
     }
+    pub mod arg {
+        pub use crate::arg::*;
+    }
     pub mod command {
         pub use crate::command::*;
     }
@@ -246,6 +542,9 @@ pub mod internal {
     pub mod flag {
         pub use crate::flag::*;
     }
+    pub mod group {
+        pub use crate::group::*;
+    }
     pub mod handler {
         pub use crate::handler::*;
     }
@@ -283,25 +582,46 @@ pub mod prelude {
     //! - `State` (Extractor)
     //! - `FlagArg` (Extractor)
     //! - `Args` (Extractor)
+    //! - `Arg` (Trait and Derive Macro)
+    //! - `ArgVal` (Extractor)
+    //! - `FlagGroup` (Trait and Derive Macro)
+    //! - `Sourced` (Extractor)
+    //! - `ParentFlag` (Extractor)
+    //! - `Confirm` (Extractor)
+    //! - `Terminal` (Extractor)
+    //! - `ValueSource` (Enum)
 
     pub use crate::app::App;
+    pub use crate::arg::{Arg, ArgDef, Arity};
     pub use crate::command::CommandDef;
+    pub use crate::confirm::Confirm;
     pub use crate::context::Context;
     pub use crate::error::{KoralError, KoralResult, KoralResultExt};
-    pub use crate::extract::{Args, Extension, FlagVal as FlagArg, FromContext, State};
+    pub use crate::extract::{
+        ArgVal, Args, Extension, FlagVal as FlagArg, FromContext, ParentFlag, Sourced, State,
+    };
     pub use crate::flag::{Flag, FlagDef};
+    pub use crate::group::{FlagGroup, GroupDef};
     pub use crate::middleware::Middleware;
+    pub use crate::output::Terminal;
+    pub use crate::provider::ValueSource;
     pub use crate::traits::{App as AppTrait, FlagValue, FromArgs};
-    pub use koral_derive::{App, Flag, FlagValue, Subcommand};
+    pub use koral_derive::{App, Arg, Flag, FlagGroup, FlagValue, Subcommand};
 }
 
 pub use app::App;
+pub use arg::{Arg, ArgDef, Arity};
 pub use command::CommandDef;
 pub use completion::{generate_to, Shell};
+pub use confirm::Confirm;
 pub use context::Context;
 pub use error::{KoralError, KoralResult, KoralResultExt};
-pub use extract::{Args, Extension, FlagVal as FlagArg, FromContext, State};
+pub use extract::{
+    ArgVal, Args, Extension, FlagVal as FlagArg, FromContext, ParentFlag, Sourced, State,
+};
 pub use flag::{Flag, FlagDef};
-pub use koral_derive::{App, Flag, FlagValue, Subcommand};
+pub use group::{FlagGroup, GroupDef};
+pub use koral_derive::{App, Arg, Flag, FlagGroup, FlagValue, Subcommand};
 pub use middleware::Middleware;
+pub use output::Terminal;
 pub use traits::{FlagValue, FromArgs};
@@ -20,14 +20,35 @@ pub fn generate_man_page<T: App + ?Sized>(app: &T, date: &str) -> String {
     // SYNOPSIS
     out.push_str(".SH SYNOPSIS\n");
     out.push_str(&format!(".B {}\n", app.name()));
-    out.push_str("[\\fIOPTIONS\\fR] [\\fICOMMAND\\fR]\n");
+    let args = crate::arg::ordered(&app.args());
+    let mut synopsis = String::from("[\\fIOPTIONS\\fR]");
+    for arg in &args {
+        synopsis.push_str(&format!(" \\fI{}\\fR", arg.placeholder()));
+    }
+    if args.is_empty() || !app.subcommands().is_empty() {
+        synopsis.push_str(" [\\fICOMMAND\\fR]");
+    }
+    out.push_str(&format!("{}\n", synopsis));
 
     // DESCRIPTION
     out.push_str(".SH DESCRIPTION\n");
     out.push_str(&format!("{}\n", app.description()));
 
+    // ARGUMENTS
+    if !args.is_empty() {
+        out.push_str(".SH ARGUMENTS\n");
+        for arg in &args {
+            out.push_str(".TP\n");
+            out.push_str(&format!("\\fI{}\\fR\n", arg.placeholder()));
+            out.push_str(&format!("{}\n", arg.help.replace('-', "\\-")));
+        }
+    }
+
     // OPTIONS
-    let flags = app.flags();
+    let mut flags = app.flags();
+    if let Some(prefix) = app.env_prefix() {
+        crate::flag::apply_env_prefix(&mut flags, prefix);
+    }
     if !flags.is_empty() {
         out.push_str(".SH OPTIONS\n");
         let mut sorted_flags = flags.clone();
@@ -40,9 +61,18 @@ pub fn generate_man_page<T: App + ?Sized>(app: &T, date: &str) -> String {
                 line.push_str(&format!("\\fB-{}\\fR, ", s));
             }
             line.push_str(&format!("\\fB--{}\\fR", flag.name));
-            if flag.takes_value {
+            if flag.negatable {
+                line.push_str(&format!(", \\fB--no-{}\\fR", flag.name));
+            }
+            if flag.takes_value && flag.missing_value.is_some() {
+                let vname = flag.value_name.as_deref().unwrap_or("value");
+                line.push_str(&format!(" [=\\fI{}\\fR]", vname));
+            } else if flag.takes_value {
                 let vname = flag.value_name.as_deref().unwrap_or("value");
                 line.push_str(&format!(" \\fI{}\\fR", vname));
+                if flag.multiple {
+                    line.push_str("...");
+                }
             }
             out.push_str(&format!("{}\n", line));
             // Basic escaping for hyphen in description
@@ -57,8 +87,10 @@ pub fn generate_man_page<T: App + ?Sized>(app: &T, date: &str) -> String {
         name: app.name().to_string(),
         description: app.description().to_string(),
         aliases: vec![],
-        subcommands: app.subcommands(),
+        subcommands: crate::external::with_plugins(app),
         flags: vec![], // handled separately
+        args: vec![],
+        external: false,
     };
 
     fn collect_subs(
@@ -120,9 +152,18 @@ pub fn generate_man_page<T: App + ?Sized>(app: &T, date: &str) -> String {
                     line.push_str(&format!("\\fB-{}\\fR, ", s));
                 }
                 line.push_str(&format!("\\fB--{}\\fR", flag.name));
-                if flag.takes_value {
+                if flag.negatable {
+                    line.push_str(&format!(", \\fB--no-{}\\fR", flag.name));
+                }
+                if flag.takes_value && flag.missing_value.is_some() {
+                    let vname = flag.value_name.as_deref().unwrap_or("value");
+                    line.push_str(&format!(" [=\\fI{}\\fR]", vname));
+                } else if flag.takes_value {
                     let vname = flag.value_name.as_deref().unwrap_or("value");
                     line.push_str(&format!(" \\fI{}\\fR", vname));
+                    if flag.multiple {
+                        line.push_str("...");
+                    }
                 }
                 out.push_str(&format!("{}\n", line));
                 let safe_help = flag.help.replace('-', "\\-");
@@ -131,5 +172,41 @@ pub fn generate_man_page<T: App + ?Sized>(app: &T, date: &str) -> String {
         }
     }
 
+    // ENVIRONMENT
+    let mut env_vars: Vec<(String, String, Option<String>)> = flags
+        .iter()
+        .filter_map(|f| f.env.clone().map(|env| (env, f.name.clone(), None)))
+        .collect();
+    for (full_name, cmd) in &all_subs {
+        let mut sub_flags = cmd.flags.clone();
+        if let Some(prefix) = app.env_prefix() {
+            let scoped = full_name.split(' ').fold(prefix.to_string(), |p, name| {
+                crate::flag::scoped_env_prefix(&p, name)
+            });
+            crate::flag::apply_env_prefix(&mut sub_flags, &scoped);
+        }
+        env_vars.extend(
+            sub_flags
+                .into_iter()
+                .filter_map(|f| f.env.map(|env| (env, f.name, Some(full_name.clone())))),
+        );
+    }
+    if !env_vars.is_empty() {
+        out.push_str(".SH ENVIRONMENT\n");
+        env_vars.sort();
+        env_vars.dedup();
+        for (env, flag, command) in env_vars {
+            out.push_str(".TP\n");
+            out.push_str(&format!("\\fB{}\\fR\n", env));
+            match command {
+                Some(command) => out.push_str(&format!(
+                    "Default for \\fB--{}\\fR of \\fB{}\\fR.\n",
+                    flag, command
+                )),
+                None => out.push_str(&format!("Default for \\fB--{}\\fR.\n", flag)),
+            }
+        }
+    }
+
     out
 }
@@ -0,0 +1,209 @@
+use crate::context::Context;
+use crate::error::KoralResult;
+use crate::extract::FromContext;
+use crate::prompt::{PromptHandle, PromptIo, TerminalIo};
+use crate::traits::App;
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::io::{IsTerminal, Write};
+use std::rc::Rc;
+
+/// Width assumed when `COLUMNS` does not say otherwise.
+const DEFAULT_WIDTH: usize = 100;
+
+/// A shared handle to a writer; clones write to the same place.
+#[derive(Clone)]
+pub struct Output(Rc<RefCell<dyn Write>>);
+
+impl Output {
+    /// Wrap `writer`.
+    pub fn new<W: Write + 'static>(writer: W) -> Self {
+        Self(Rc::new(RefCell::new(writer)))
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.borrow_mut().flush()
+    }
+}
+
+impl std::fmt::Debug for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Output")
+    }
+}
+
+/// An in-memory writer whose contents can be read back, e.g. to capture output.
+#[derive(Clone, Debug, Default)]
+pub struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+impl Buffer {
+    /// Create an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything written so far.
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.borrow()).into_owned()
+    }
+}
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Where a command writes its output and reads its answers, and how.
+///
+/// Help, version and prompts go through the terminal of the running command;
+/// handlers reach it with `ctx.terminal()` or the `Terminal` extractor.
+#[derive(Clone, Debug)]
+pub struct Terminal {
+    stdout: Output,
+    stderr: Output,
+    color: bool,
+    width: usize,
+    input: Option<PromptHandle>,
+}
+
+impl Terminal {
+    /// The process's stdout and stderr.
+    ///
+    /// Colored when stdout is a terminal and `NO_COLOR` is unset; as wide as `COLUMNS` says.
+    pub fn stdio() -> Self {
+        let color = std::io::stdout().is_terminal()
+            && crate::host::var_os("NO_COLOR").is_none_or(|v| v.is_empty());
+        Self::new(std::io::stdout(), std::io::stderr())
+            .with_color(color)
+            .with_width(default_width())
+    }
+
+    /// Write to `stdout` and `stderr`, without color.
+    pub fn new<O: Write + 'static, E: Write + 'static>(stdout: O, stderr: E) -> Self {
+        Self {
+            stdout: Output::new(stdout),
+            stderr: Output::new(stderr),
+            color: false,
+            width: DEFAULT_WIDTH,
+            input: None,
+        }
+    }
+
+    /// Set whether ANSI styles are kept.
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the width text is wrapped to.
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Answer prompts from `input` instead of the process's terminal.
+    pub fn with_input<P: PromptIo + 'static>(mut self, input: P) -> Self {
+        self.input = Some(PromptHandle::new(input));
+        self
+    }
+
+    /// Handle to standard output.
+    pub fn stdout(&self) -> Output {
+        self.stdout.clone()
+    }
+
+    /// Handle to standard error.
+    pub fn stderr(&self) -> Output {
+        self.stderr.clone()
+    }
+
+    /// Whether ANSI styles are kept.
+    pub fn color(&self) -> bool {
+        self.color
+    }
+
+    /// Width text is wrapped to.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Write `text` to stdout, removing ANSI styles unless color is on.
+    pub fn print(&self, text: &str) -> KoralResult<()> {
+        let mut out = self.stdout();
+        if self.color {
+            out.write_all(text.as_bytes())?;
+        } else {
+            write!(out, "{}", anstream::adapter::strip_str(text))?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Report `err` on stderr as `Error: ...`.
+    pub fn print_error(&self, err: &dyn Display) -> KoralResult<()> {
+        let style = anstyle::Style::new()
+            .bold()
+            .fg_color(Some(anstyle::Color::Ansi(anstyle::AnsiColor::Red)));
+        let mut out = self.stderr();
+        if self.color {
+            writeln!(out, "{style}Error:{style:#} {}", err)?;
+        } else {
+            writeln!(out, "Error: {}", err)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Where prompts are answered.
+    pub(crate) fn input(&self) -> PromptHandle {
+        self.input
+            .clone()
+            .unwrap_or_else(|| PromptHandle::new(TerminalIo))
+    }
+}
+
+impl Default for Terminal {
+    fn default() -> Self {
+        Self::stdio()
+    }
+}
+
+impl<'a> FromContext<'a> for Terminal {
+    fn from_context(ctx: &'a Context) -> KoralResult<Self> {
+        Ok(ctx.terminal())
+    }
+}
+
+/// Run `app` with `args`, writing to and reading from `terminal`.
+pub fn run_with_terminal<A: App + ?Sized>(
+    app: &mut A,
+    terminal: Terminal,
+    state: Option<&mut dyn Any>,
+    args: Vec<String>,
+) -> KoralResult<()> {
+    let inherited = crate::traits::Inherited {
+        terminal: Some(terminal),
+        ..Default::default()
+    };
+    app.run_nested(state, args, inherited)
+}
+
+/// Terminal width from `COLUMNS`, or 100 columns.
+pub(crate) fn default_width() -> usize {
+    crate::host::var("COLUMNS")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
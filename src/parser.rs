@@ -2,11 +2,65 @@ use crate::context::Context;
 use crate::error::{KoralError, KoralResult};
 use std::collections::HashMap;
 
+/// Flag values collected while walking the arguments.
+#[derive(Default)]
+struct ParsedFlags {
+    /// Last value seen for each flag (`None` for boolean flags).
+    flags: HashMap<String, Option<String>>,
+    /// Every value seen for flags accepting multiple values.
+    values: HashMap<String, Vec<String>>,
+    /// Where each flag value came from.
+    sources: HashMap<String, crate::provider::ValueSource>,
+}
+
+impl ParsedFlags {
+    fn contains(&self, name: &str) -> bool {
+        self.flags.contains_key(name)
+    }
+
+    /// Record an occurrence of a flag.
+    /// Multi-valued flags accumulate, counting flags increment,
+    /// others keep the last value.
+    fn insert(&mut self, flag: &crate::flag::FlagDef, val: Option<String>) {
+        if flag.count {
+            let current = self
+                .flags
+                .get(&flag.name)
+                .and_then(|v| v.as_deref())
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0);
+            self.flags
+                .insert(flag.name.clone(), Some((current + 1).to_string()));
+            return;
+        }
+        if flag.multiple {
+            if let Some(v) = &val {
+                self.values
+                    .entry(flag.name.clone())
+                    .or_default()
+                    .extend(flag.split_value(v));
+            }
+        }
+        self.flags.insert(flag.name.clone(), val);
+    }
+
+    /// Record the final state of a negatable flag, replacing earlier occurrences.
+    fn set_bool(&mut self, flag: &crate::flag::FlagDef, on: bool) {
+        self.flags.insert(flag.name.clone(), Some(on.to_string()));
+    }
+}
+
 /// Command line argument parser
 pub struct Parser {
     known_flags: Vec<crate::flag::FlagDef>,
+    known_args: Vec<crate::arg::ArgDef>,
+    known_groups: Vec<crate::group::GroupDef>,
+    providers: Vec<Box<dyn crate::provider::ValueProvider>>,
+    config: Option<crate::provider::FileProvider>,
     strict: bool,
     ignore_required: bool,
+    infer_long_args: bool,
+    external_subcommands: Option<Vec<crate::command::CommandDef>>,
 }
 
 impl Parser {
@@ -14,8 +68,14 @@ impl Parser {
     pub fn new(flags: Vec<crate::flag::FlagDef>) -> Self {
         Self {
             known_flags: flags,
+            known_args: Vec::new(),
+            known_groups: Vec::new(),
+            providers: crate::provider::default_providers(),
+            config: None,
             strict: false,
             ignore_required: false,
+            infer_long_args: false,
+            external_subcommands: None,
         }
     }
 
@@ -25,6 +85,49 @@ impl Parser {
         self
     }
 
+    /// Helper to declare positional arguments
+    pub fn args(mut self, args: Vec<crate::arg::ArgDef>) -> Self {
+        self.known_args = args;
+        self
+    }
+
+    /// Helper to declare flag groups
+    pub fn groups(mut self, groups: Vec<crate::group::GroupDef>) -> Self {
+        self.known_groups = groups;
+        self
+    }
+
+    /// Helper to replace the sources consulted for flags missing from the command line.
+    ///
+    /// Providers are tried in order. The configuration file and flag defaults are not
+    /// providers: they always come after, in that order.
+    pub fn with_providers(
+        mut self,
+        providers: Vec<Box<dyn crate::provider::ValueProvider>>,
+    ) -> Self {
+        self.providers = providers;
+        self
+    }
+
+    /// Helper to read missing values from a configuration file
+    pub fn config(mut self, config: Option<crate::provider::FileProvider>) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Helper to accept unambiguous prefixes of long flags (`--verb` for `--verbose`)
+    pub fn infer_long_args(mut self, infer: bool) -> Self {
+        self.infer_long_args = infer;
+        self
+    }
+
+    /// Helper to pass a first positional that is none of the `known` subcommands,
+    /// and every argument after it, through unparsed to an external subcommand
+    pub fn external_subcommands(mut self, known: Option<Vec<crate::command::CommandDef>>) -> Self {
+        self.external_subcommands = known;
+        self
+    }
+
     /// Helper to set whether to ignore required flags (e.g. for help)
     pub fn ignore_required(mut self, ignore: bool) -> Self {
         self.ignore_required = ignore;
@@ -33,7 +136,7 @@ impl Parser {
 
     /// Parse the provided arguments into a Context.
     pub fn parse<'a>(&self, args: &[String]) -> KoralResult<Context<'a>> {
-        let mut flags_map: HashMap<String, Option<String>> = HashMap::new();
+        let mut parsed = ParsedFlags::default();
 
         let mut positionals: Vec<String> = Vec::new();
 
@@ -49,22 +152,63 @@ impl Parser {
 
             if arg.starts_with("--") {
                 // Long flag
-                self.parse_long_flag(arg, &mut iter, &mut flags_map, &mut positionals)?;
+                self.parse_long_flag(arg, &mut iter, &mut parsed, &mut positionals)?;
             } else if arg.starts_with('-') && arg.len() > 1 {
                 // Short flag (potentially combined)
-                self.parse_short_flags(arg, &mut iter, &mut flags_map, &mut positionals)?;
+                self.parse_short_flags(arg, &mut iter, &mut parsed, &mut positionals)?;
             } else {
                 positionals.push(arg.clone());
+                if positionals.len() == 1 && self.is_external(arg) {
+                    positionals.extend(iter.cloned());
+                    break;
+                }
             }
         }
 
+        // Conflicts only concern flags given on the command line
+        let explicit: Vec<String> = parsed.flags.keys().cloned().collect();
+        for name in &explicit {
+            parsed
+                .sources
+                .insert(name.clone(), crate::provider::ValueSource::CommandLine);
+        }
+        self.load_file_values(&mut parsed, &explicit)?;
+
         // Fill in default values if missing
-        self.apply_defaults(&mut flags_map);
+        self.apply_defaults(&mut parsed)?;
 
         // Validate flags
-        self.validate_constraints(&flags_map)?;
+        self.validate_constraints(&parsed, &explicit)?;
+
+        // Bind positionals to declared arguments
+        let arg_values = crate::arg::bind(&self.known_args, &positionals);
+        if self.ignore_required {
+            validate_arg_types(&self.known_args, &arg_values)?;
+        } else {
+            validate_args(&self.known_args, &positionals, &arg_values)?;
+        }
 
-        Ok(Context::new(flags_map, positionals))
+        Ok(Context::new(parsed.flags, positionals)
+            .with_values(parsed.values)
+            .with_sources(parsed.sources)
+            .with_sensitive(
+                self.known_flags
+                    .iter()
+                    .filter(|f| f.sensitive)
+                    .map(|f| f.name.clone())
+                    .collect(),
+            )
+            .with_arg_values(arg_values))
+    }
+
+    fn is_external(&self, name: &str) -> bool {
+        match &self.external_subcommands {
+            Some(known) => matches!(
+                crate::command::resolve_subcommand(known, name, true),
+                Ok(None)
+            ),
+            None => false,
+        }
     }
 
     fn find_short_flag(&self, c: char) -> Option<&crate::flag::FlagDef> {
@@ -82,7 +226,7 @@ impl Parser {
         &self,
         arg: &str,
         iter: &mut I,
-        flags_map: &mut HashMap<String, Option<String>>,
+        parsed: &mut ParsedFlags,
         positionals: &mut Vec<String>,
     ) -> KoralResult<()>
     where
@@ -106,12 +250,45 @@ impl Parser {
                 break;
             }
         }
+        if matched_flag.is_none() && self.infer_long_args {
+            matched_flag = self.infer_long_flag(name_part)?;
+        }
+
+        // --no-key for negatable flags
+        if matched_flag.is_none() {
+            if let Some(negated) = name_part.strip_prefix("no-") {
+                let negatable = self
+                    .known_flags
+                    .iter()
+                    .find(|f| f.negatable && f.long.as_deref().unwrap_or(&f.name) == negated);
+                if let Some(flag) = negatable {
+                    if value_part.is_some() {
+                        return Err(KoralError::Validation(format!(
+                            "Flag '--no-{}' does not take a value",
+                            flag.name
+                        )));
+                    }
+                    parsed.set_bool(flag, false);
+                    return Ok(());
+                }
+            }
+        }
 
         if let Some(flag) = matched_flag {
             if let Some(val) = value_part {
                 // --key=value
                 if flag.takes_value {
-                    flags_map.insert(flag.name.clone(), Some(val.to_string()));
+                    parsed.insert(flag, Some(val.to_string()));
+                } else if flag.negatable {
+                    match val.to_lowercase().parse::<bool>() {
+                        Ok(on) => parsed.set_bool(flag, on),
+                        Err(_) => {
+                            return Err(KoralError::Validation(format!(
+                                "Invalid value for flag '--{}': expected 'true' or 'false', got '{}'",
+                                flag.name, val
+                            )));
+                        }
+                    }
                 } else {
                     return Err(KoralError::Validation(format!(
                         "Flag '--{}' does not take a value",
@@ -120,7 +297,7 @@ impl Parser {
                 }
             } else {
                 // --key (consume next if needed)
-                self.consume_flag_value(flag, iter, flags_map)?;
+                self.consume_flag_value(flag, iter, parsed)?;
             }
         } else {
             // Unknown long flag
@@ -137,11 +314,39 @@ impl Parser {
         Ok(())
     }
 
+    /// Find the only flag whose long name or alias starts with `prefix`.
+    fn infer_long_flag(&self, prefix: &str) -> KoralResult<Option<&crate::flag::FlagDef>> {
+        if prefix.is_empty() {
+            return Ok(None);
+        }
+        let candidates: Vec<&crate::flag::FlagDef> = self
+            .known_flags
+            .iter()
+            .filter(|f| {
+                f.long.as_deref().unwrap_or(&f.name).starts_with(prefix)
+                    || f.aliases.iter().any(|a| a.starts_with(prefix))
+            })
+            .collect();
+        match candidates.as_slice() {
+            [] => Ok(None),
+            [flag] => Ok(Some(flag)),
+            _ => Err(KoralError::InvalidFlag(format!(
+                "Flag '--{}' is ambiguous\n\t[candidates: {}]",
+                prefix,
+                candidates
+                    .iter()
+                    .map(|f| format!("--{}", f.long.as_deref().unwrap_or(&f.name)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
     fn parse_short_flags<'a, I>(
         &self,
         arg: &str,
         iter: &mut I,
-        flags_map: &mut HashMap<String, Option<String>>,
+        parsed: &mut ParsedFlags,
         positionals: &mut Vec<String>,
     ) -> KoralResult<()>
     where
@@ -219,14 +424,10 @@ impl Parser {
         if valid_group {
             // Apply plan
             for item in plan {
-                if let Some(val) = item.val {
-                    flags_map.insert(item.flag.name.clone(), Some(val));
-                } else {
-                    flags_map.insert(item.flag.name.clone(), None);
-                }
+                parsed.insert(item.flag, item.val);
             }
             if let Some(flag) = consume_next {
-                self.consume_flag_value(flag, iter, flags_map)?;
+                self.consume_flag_value(flag, iter, parsed)?;
             }
         } else {
             // Treated as positional in non-strict mode
@@ -239,14 +440,17 @@ impl Parser {
         &self,
         flag: &crate::flag::FlagDef,
         iter: &mut I,
-        flags_map: &mut HashMap<String, Option<String>>,
+        parsed: &mut ParsedFlags,
     ) -> KoralResult<()>
     where
         I: Iterator<Item = &'a String>,
     {
-        if flag.takes_value {
+        if let Some(missing) = &flag.missing_value {
+            // Optional value: only `--flag=value` sets it explicitly
+            parsed.insert(flag, Some(missing.clone()));
+        } else if flag.takes_value {
             if let Some(val) = iter.next() {
-                flags_map.insert(flag.name.clone(), Some(val.clone()));
+                parsed.insert(flag, Some(val.clone()));
             } else {
                 return Err(KoralError::MissingArgument(format!(
                     "Flag '--{}' requires a value",
@@ -255,51 +459,149 @@ impl Parser {
             }
         } else {
             // Boolean flag
-            flags_map.insert(flag.name.clone(), None);
+            parsed.insert(flag, None);
         }
         Ok(())
     }
 
-    fn apply_defaults(&self, flags_map: &mut HashMap<String, Option<String>>) {
-        use crate::provider::{DefaultProvider, EnvProvider, ValueProvider};
+    /// Replace `@path` and `-` values of `from_file` flags with the content read.
+    fn load_file_values(&self, parsed: &mut ParsedFlags, explicit: &[String]) -> KoralResult<()> {
+        let from_file = self
+            .known_flags
+            .iter()
+            .filter(|f| f.from_file && explicit.contains(&f.name));
+        for flag in from_file {
+            if let Some(values) = parsed.values.get_mut(&flag.name) {
+                for value in values.iter_mut() {
+                    *value = read_value_file(value)?;
+                }
+                let last = values.last().cloned();
+                parsed.flags.insert(flag.name.clone(), last);
+            } else if let Some(Some(value)) = parsed.flags.get_mut(&flag.name) {
+                *value = read_value_file(value)?;
+            }
+        }
+        Ok(())
+    }
 
-        let providers: Vec<Box<dyn ValueProvider>> =
-            vec![Box::new(EnvProvider), Box::new(DefaultProvider)];
+    fn apply_defaults(&self, parsed: &mut ParsedFlags) -> KoralResult<()> {
+        use crate::provider::{DefaultProvider, ValueProvider, ValueSource};
 
-        for flag in &self.known_flags {
-            if !flags_map.contains_key(&flag.name) {
-                // Try providers in order
-                for provider in &providers {
-                    if let Some(val) = provider.get_value(flag) {
-                        if flag.takes_value {
-                            flags_map.insert(flag.name.clone(), Some(val));
-                        } else {
-                            // For boolean flags from env/defaults
-                            // If val is "true" or non-empty/non-zero
-                            if val != "0" && val.to_lowercase() != "false" && !val.is_empty() {
-                                flags_map.insert(flag.name.clone(), None);
+        let provided_or_file = |flag: &crate::flag::FlagDef| -> KoralResult<_> {
+            for provider in &self.providers {
+                if let Some(vals) = provider.try_get_values(flag)? {
+                    return Ok(Some((vals, provider.source(flag))));
+                }
+            }
+            Ok(self
+                .config
+                .as_ref()
+                .and_then(|config| config.get_values(flag).map(|v| (v, config.source(flag)))))
+        };
+
+        // Flags with conditional defaults go last so their conditions see final values
+        let (plain, conditional): (Vec<_>, Vec<_>) = self
+            .known_flags
+            .iter()
+            .partition(|f| f.default_value_if.is_empty());
+
+        for flag in plain.into_iter().chain(conditional) {
+            if !parsed.contains(&flag.name) {
+                // Providers (the environment by default), then the config file,
+                // then conditional and static defaults
+                let provided = provided_or_file(flag)?
+                    .or_else(|| {
+                        conditional_default(flag, &parsed.flags)
+                            .map(|v| (vec![v], ValueSource::Default))
+                    })
+                    .or_else(|| {
+                        DefaultProvider
+                            .get_values(flag)
+                            .map(|v| (v, DefaultProvider.source(flag)))
+                    });
+                if let Some((vals, source)) = provided {
+                    if flag.takes_value {
+                        for val in vals {
+                            parsed.insert(flag, Some(val));
+                        }
+                    } else if let Some(val) = vals.into_iter().last() {
+                        // For boolean flags from env/defaults
+                        // If val is "true" or non-empty/non-zero
+                        if is_truthy(&val) {
+                            if flag.count {
+                                // Provided values are the count itself, e.g. VERBOSE=2
+                                parsed.flags.insert(flag.name.clone(), Some(val));
+                            } else {
+                                parsed.insert(flag, None);
                             }
+                        } else if flag.negatable
+                            && flag.default_value.as_deref().is_some_and(is_truthy)
+                        {
+                            // An explicit "false" must win over a default that turns it on
+                            parsed.set_bool(flag, false);
                         }
-                        // Once found, stop checking other providers
-                        break;
+                    }
+                    if parsed.contains(&flag.name) {
+                        parsed.sources.insert(flag.name.clone(), source);
                     }
                 }
             }
         }
+        Ok(())
     }
 
-    fn validate_constraints(&self, flags_map: &HashMap<String, Option<String>>) -> KoralResult<()> {
-        for flag in &self.known_flags {
-            // Check required
-            if !self.ignore_required && flag.required && !flags_map.contains_key(&flag.name) {
+    fn validate_constraints(&self, parsed: &ParsedFlags, explicit: &[String]) -> KoralResult<()> {
+        // A switch turned off with `--no-x` or `--x=false` is not in use
+        let off = |name: &str| switched_off(&self.known_flags, &parsed.flags, name);
+        let given = |name: &str| explicit.iter().any(|e| e == name) && !off(name);
+
+        for flag in self.known_flags.iter().filter(|f| given(&f.name)) {
+            if let Some(other) = flag.conflicts_with.iter().find(|c| given(c)) {
+                return Err(KoralError::Validation(format!(
+                    "Flag '--{}' cannot be used with '--{}'",
+                    flag.name, other
+                )));
+            }
+            if let Some(missing) = flag.requires.iter().find(|r| !parsed.contains(r) || off(r)) {
                 return Err(KoralError::MissingArgument(format!(
-                    "Required flag '--{}' is missing",
-                    flag.name
+                    "Flag '--{}' requires '--{}'",
+                    flag.name, missing
                 )));
             }
+        }
+
+        for group in self.known_groups.iter().filter(|g| !g.multiple) {
+            let used: Vec<&String> = group.flags.iter().filter(|f| given(f)).collect();
+            if used.len() > 1 {
+                return Err(KoralError::Validation(format!(
+                    "Flags '--{}' and '--{}' cannot be used together (group '{}')",
+                    used[0], used[1], group.name
+                )));
+            }
+        }
+
+        if !self.ignore_required {
+            validate_required_groups(&self.known_groups, &self.known_flags, &parsed.flags)?;
+        }
+
+        if !self.ignore_required {
+            validate_required_flags(&self.known_flags, &parsed.flags)?;
+        }
+
+        for flag in &self.known_flags {
+            let values: Vec<&String> = match parsed.values.get(&flag.name) {
+                Some(values) => values.iter().collect(),
+                None => parsed.flags.get(&flag.name).into_iter().flatten().collect(),
+            };
+
+            if flag.takes_value && !flag.possible_values.is_empty() {
+                for val in &values {
+                    check_possible_value(flag, val)?;
+                }
+            }
 
             if let Some(validator) = flag.validator {
-                if let Some(Some(val)) = flags_map.get(&flag.name) {
+                for val in &values {
                     if let Err(e) = validator(val) {
                         return Err(KoralError::Validation(format!(
                             "Invalid value for flag '{}': {}",
@@ -338,6 +640,54 @@ impl Parser {
     }
 }
 
+/// Reject values outside a flag's possible values, suggesting the closest one.
+fn check_possible_value(flag: &crate::flag::FlagDef, val: &str) -> KoralResult<()> {
+    if flag
+        .possible_values
+        .iter()
+        .any(|p| p.eq_ignore_ascii_case(val))
+    {
+        return Ok(());
+    }
+    let mut msg = format!(
+        "Invalid value '{}' for flag '--{}'\n\t[possible values: {}]",
+        val,
+        flag.name,
+        flag.possible_values.join(", ")
+    );
+    let closest = flag
+        .possible_values
+        .iter()
+        .map(|p| (levenshtein(&val.to_lowercase(), &p.to_lowercase()), p))
+        .filter(|(dist, _)| *dist <= 3)
+        .min_by_key(|(dist, _)| *dist);
+    if let Some((_, sugg)) = closest {
+        msg.push_str(&format!("\n\tDid you mean '{}'?", sugg));
+    }
+    Err(KoralError::Validation(msg))
+}
+
+/// Whether `name` is a boolean flag recorded as off, e.g. by `--no-json`.
+fn switched_off(
+    flags: &[crate::flag::FlagDef],
+    flags_map: &HashMap<String, Option<String>>,
+    name: &str,
+) -> bool {
+    let is_switch = flags
+        .iter()
+        .any(|f| f.name == name && !f.takes_value && !f.count);
+    is_switch
+        && flags_map
+            .get(name)
+            .and_then(|v| v.as_deref())
+            .is_some_and(|v| !is_truthy(v))
+}
+
+/// Whether a provided value switches a boolean flag on.
+fn is_truthy(val: &str) -> bool {
+    val != "0" && val.to_lowercase() != "false" && !val.is_empty()
+}
+
 fn levenshtein(a: &str, b: &str) -> usize {
     let len_a = a.chars().count();
     let len_b = b.chars().count();
@@ -350,11 +700,11 @@ fn levenshtein(a: &str, b: &str) -> usize {
 
     let mut matrix = vec![vec![0; len_b + 1]; len_a + 1];
 
-    for i in 0..=len_a {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
-    for j in 0..=len_b {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     for (i, ca) in a.chars().enumerate() {
@@ -375,13 +725,147 @@ pub fn validate_required_flags(
     flags: &[crate::flag::FlagDef],
     flags_map: &HashMap<String, Option<String>>,
 ) -> KoralResult<()> {
-    for flag in flags {
-        if flag.required && !flags_map.contains_key(&flag.name) {
+    for flag in flags.iter().filter(|f| !flags_map.contains_key(&f.name)) {
+        if flag.required {
             return Err(KoralError::MissingArgument(format!(
                 "Required flag '--{}' is missing",
                 flag.name
             )));
         }
+        for (other, expected) in &flag.required_if_eq {
+            if flag_value(flags_map, other) == Some(expected.as_str()) {
+                return Err(KoralError::MissingArgument(format!(
+                    "Flag '--{}' is required when '--{}' is '{}'",
+                    flag.name, other, expected
+                )));
+            }
+        }
+        if !flag.required_unless_present.is_empty()
+            && !flag
+                .required_unless_present
+                .iter()
+                .any(|other| flags_map.contains_key(other))
+        {
+            let others = flag
+                .required_unless_present
+                .iter()
+                .map(|o| format!("'--{}'", o))
+                .collect::<Vec<_>>()
+                .join(" or ");
+            return Err(KoralError::MissingArgument(format!(
+                "Flag '--{}' is required unless {} is present",
+                flag.name, others
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Value of a flag as seen by conditions; boolean flags read as "true".
+fn flag_value<'m>(flags_map: &'m HashMap<String, Option<String>>, name: &str) -> Option<&'m str> {
+    flags_map.get(name).map(|v| v.as_deref().unwrap_or("true"))
+}
+
+/// Content of the file named by `@path`, or of stdin for `-`, trimmed.
+/// Other values are returned unchanged.
+fn read_value_file(value: &str) -> KoralResult<String> {
+    let content = if value == "-" {
+        crate::host::read_stdin()
+            .map_err(|e| KoralError::IoError(format!("Failed to read stdin: {}", e)))?
+    } else if let Some(path) = value.strip_prefix('@') {
+        std::fs::read_to_string(crate::host::resolve(path))
+            .map_err(|e| KoralError::IoError(format!("Failed to read {}: {}", path, e)))?
+    } else {
+        return Ok(value.to_string());
+    };
+    Ok(content.trim().to_string())
+}
+
+/// First `default_value_if` entry whose condition holds.
+fn conditional_default(
+    flag: &crate::flag::FlagDef,
+    flags_map: &HashMap<String, Option<String>>,
+) -> Option<String> {
+    flag.default_value_if
+        .iter()
+        .find(|(other, expected, _)| flag_value(flags_map, other) == Some(expected.as_str()))
+        .map(|(_, _, default)| default.clone())
+}
+
+/// Helper function to validate required flag groups externally.
+/// Used by generated App code alongside `validate_required_flags`.
+pub fn validate_required_groups(
+    groups: &[crate::group::GroupDef],
+    flags: &[crate::flag::FlagDef],
+    flags_map: &HashMap<String, Option<String>>,
+) -> KoralResult<()> {
+    for group in groups {
+        let in_use = |f: &String| flags_map.contains_key(f) && !switched_off(flags, flags_map, f);
+        if group.required && !group.flags.iter().any(in_use) {
+            return Err(KoralError::MissingArgument(format!(
+                "One of {} is required",
+                group.describe()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Helper function to validate positional arguments externally.
+/// Used by generated App code alongside `validate_required_flags`.
+///
+/// Fails when a required argument is missing, when a value does not convert
+/// to its argument's type, or when values are left over once every declared
+/// argument is served. Apps declaring no arguments take any positionals.
+pub fn validate_args(
+    args: &[crate::arg::ArgDef],
+    positionals: &[String],
+    arg_values: &HashMap<String, Vec<String>>,
+) -> KoralResult<()> {
+    validate_required_args(args, arg_values)?;
+    validate_arg_types(args, arg_values)?;
+    let bound: usize = arg_values.values().map(Vec::len).sum();
+    if !args.is_empty() && positionals.len() > bound {
+        return Err(KoralError::InvalidArgument(format!(
+            "Unexpected argument '{}'",
+            positionals[bound]
+        )));
+    }
+    Ok(())
+}
+
+/// Convert the values bound to each argument, as extraction will.
+fn validate_arg_types(
+    args: &[crate::arg::ArgDef],
+    arg_values: &HashMap<String, Vec<String>>,
+) -> KoralResult<()> {
+    for arg in args {
+        let (Some(check), Some(values)) = (arg.check, arg_values.get(&arg.name)) else {
+            continue;
+        };
+        let values: Vec<&str> = values.iter().map(|v| v.as_str()).collect();
+        check(&values).map_err(|e| {
+            KoralError::FlagValueParseError(format!(
+                "Invalid value for argument '{}': {}",
+                arg.name, e
+            ))
+        })?;
+    }
+    Ok(())
+}
+
+/// Helper function to validate required positional arguments externally.
+pub fn validate_required_args(
+    args: &[crate::arg::ArgDef],
+    arg_values: &HashMap<String, Vec<String>>,
+) -> KoralResult<()> {
+    for arg in crate::arg::ordered(args) {
+        if arg.required && !arg_values.contains_key(&arg.name) {
+            return Err(KoralError::MissingArgument(format!(
+                "Required argument '{}' is missing",
+                arg.placeholder()
+            )));
+        }
     }
     Ok(())
 }
@@ -406,6 +890,21 @@ mod tests {
             aliases: vec![],
             value_name: None,
             help_heading: None,
+            multiple: false,
+            value_delimiter: None,
+            count: false,
+            negatable: false,
+            missing_value: None,
+            conflicts_with: vec![],
+            requires: vec![],
+            required_if_eq: vec![],
+            required_unless_present: vec![],
+            default_value_if: vec![],
+            possible_values: vec![],
+            from_file: false,
+            sensitive: false,
+            global: false,
+            check: None,
         };
         let opt_flag = FlagDef {
             name: "opt".to_string(),
@@ -420,6 +919,21 @@ mod tests {
             aliases: vec![],
             value_name: None,
             help_heading: None,
+            multiple: false,
+            value_delimiter: None,
+            count: false,
+            negatable: false,
+            missing_value: None,
+            conflicts_with: vec![],
+            requires: vec![],
+            required_if_eq: vec![],
+            required_unless_present: vec![],
+            default_value_if: vec![],
+            possible_values: vec![],
+            from_file: false,
+            sensitive: false,
+            global: false,
+            check: None,
         };
 
         let flags = vec![req_flag, opt_flag];
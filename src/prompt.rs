@@ -0,0 +1,290 @@
+use crate::context::Context;
+use crate::error::KoralResult;
+use crate::flag::FlagDef;
+use crate::provider::ValueSource;
+use crate::traits::App;
+use std::any::Any;
+use std::cell::RefCell;
+use std::io::{BufRead, IsTerminal, Write};
+use std::rc::Rc;
+
+/// Name of the flag that turns prompting off.
+pub const NO_INPUT_FLAG: &str = "no-input";
+
+/// Terminal through which missing values are asked for.
+pub trait PromptIo {
+    /// Whether someone can answer; nothing is asked otherwise.
+    fn is_interactive(&self) -> bool;
+
+    /// Show `text` to the user.
+    fn write(&mut self, text: &str) -> KoralResult<()>;
+
+    /// Read a line of input, without its line ending; `None` at the end of input.
+    fn read_line(&mut self) -> KoralResult<Option<String>>;
+
+    /// Read a line of input without showing it.
+    fn read_secret(&mut self) -> KoralResult<Option<String>> {
+        self.read_line()
+    }
+}
+
+/// Asks on stderr and reads answers from stdin.
+///
+/// Interactive only when both are terminals.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalIo;
+
+impl PromptIo for TerminalIo {
+    fn is_interactive(&self) -> bool {
+        std::io::stdin().is_terminal() && std::io::stderr().is_terminal()
+    }
+
+    fn write(&mut self, text: &str) -> KoralResult<()> {
+        let mut stderr = std::io::stderr();
+        stderr.write_all(text.as_bytes())?;
+        stderr.flush()?;
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> KoralResult<Option<String>> {
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let len = line.trim_end_matches(['\r', '\n']).len();
+        line.truncate(len);
+        Ok(Some(line))
+    }
+
+    /// Turns echo off on stdin while reading; a secret is never read when that fails.
+    #[cfg(unix)]
+    fn read_secret(&mut self) -> KoralResult<Option<String>> {
+        use std::os::fd::AsRawFd;
+        let fd = std::io::stdin().as_raw_fd();
+        let mut original = std::mem::MaybeUninit::<libc::termios>::uninit();
+        // SAFETY: `tcgetattr` fills `original` when it returns 0
+        if unsafe { libc::tcgetattr(fd, original.as_mut_ptr()) } != 0 {
+            return Err(hidden_input_error());
+        }
+        // SAFETY: initialized by the successful `tcgetattr` above
+        let original = unsafe { original.assume_init() };
+        let mut hidden = original;
+        hidden.c_lflag &= !libc::ECHO;
+        // The newline typed is still shown
+        hidden.c_lflag |= libc::ECHONL;
+        // SAFETY: `hidden` is a valid termios read from the same descriptor
+        if unsafe { libc::tcsetattr(fd, libc::TCSAFLUSH, &hidden) } != 0 {
+            return Err(hidden_input_error());
+        }
+        let line = self.read_line();
+        // SAFETY: restores the settings read above
+        unsafe { libc::tcsetattr(fd, libc::TCSAFLUSH, &original) };
+        line
+    }
+
+    #[cfg(not(unix))]
+    fn read_secret(&mut self) -> KoralResult<Option<String>> {
+        Err(hidden_input_error())
+    }
+}
+
+fn hidden_input_error() -> crate::KoralError {
+    crate::KoralError::IoError("Cannot hide input typed on this terminal".to_string())
+}
+
+/// Prompt IO shared by a command and the subcommands it dispatches to.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct PromptHandle(Rc<RefCell<dyn PromptIo>>);
+
+impl PromptHandle {
+    pub fn new<P: PromptIo + 'static>(io: P) -> Self {
+        Self(Rc::new(RefCell::new(io)))
+    }
+
+    /// The IO of the terminal `ctx` runs in.
+    pub(crate) fn of(ctx: &Context) -> Self {
+        ctx.terminal().input()
+    }
+
+    pub(crate) fn borrow_mut(&self) -> std::cell::RefMut<'_, dyn PromptIo> {
+        self.0.borrow_mut()
+    }
+}
+
+impl std::fmt::Debug for PromptHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PromptHandle")
+    }
+}
+
+/// Run `app` with `args`, asking `io` instead of the terminal for missing values.
+///
+/// Prompting must be enabled with `#[app(prompt_missing)]` as usual.
+pub fn run_with_io<A: App + ?Sized, P: PromptIo + 'static>(
+    app: &mut A,
+    io: P,
+    state: Option<&mut dyn Any>,
+    args: Vec<String>,
+) -> KoralResult<()> {
+    let terminal = crate::output::Terminal::stdio().with_input(io);
+    crate::output::run_with_terminal(app, terminal, state, args)
+}
+
+/// The `--no-input` flag added by `#[app(prompt_missing)]`.
+#[doc(hidden)]
+pub fn no_input_flag() -> FlagDef {
+    FlagDef {
+        name: NO_INPUT_FLAG.to_string(),
+        long: Some(NO_INPUT_FLAG.to_string()),
+        short: None,
+        help: "Never prompt for missing values".to_string(),
+        takes_value: false,
+        required: false,
+        default_value: None,
+        env: None,
+        validator: None,
+        aliases: vec![],
+        value_name: None,
+        help_heading: None,
+        multiple: false,
+        value_delimiter: None,
+        count: false,
+        negatable: false,
+        missing_value: None,
+        conflicts_with: vec![],
+        requires: vec![],
+        required_if_eq: vec![],
+        required_unless_present: vec![],
+        default_value_if: vec![],
+        possible_values: vec![],
+        from_file: false,
+        sensitive: false,
+        global: true,
+        check: None,
+    }
+}
+
+/// Ask for every required flag missing from `parsed`.
+///
+/// Optional flags are never asked for. Each answer must convert to the flag's
+/// type and pass its validator; the flag is asked for again until it does.
+/// Does nothing unless `io` is interactive. Stops at the end of input,
+/// leaving the remaining flags for validation to report.
+pub(crate) fn fill_missing(
+    flags: &[FlagDef],
+    parsed: &mut Context,
+    io: &PromptHandle,
+) -> KoralResult<()> {
+    let mut io = io.borrow_mut();
+    if !io.is_interactive() {
+        return Ok(());
+    }
+    for flag in flags.iter().filter(|f| f.required) {
+        if parsed.flags.contains_key(&flag.name) {
+            continue;
+        }
+        let answer = if !flag.takes_value {
+            ask_confirm(flag, &mut *io)?.map(|on| on.to_string())
+        } else if !flag.possible_values.is_empty() {
+            ask_select(flag, &mut *io)?
+        } else {
+            ask_value(flag, &mut *io)?
+        };
+        let Some(answer) = answer else {
+            return Ok(());
+        };
+        if flag.multiple && flag.takes_value {
+            parsed
+                .values
+                .insert(flag.name.clone(), flag.split_value(&answer));
+        }
+        parsed.flags.insert(flag.name.clone(), Some(answer));
+        parsed
+            .sources
+            .insert(flag.name.clone(), ValueSource::Prompt);
+    }
+    Ok(())
+}
+
+fn label(flag: &FlagDef) -> String {
+    if flag.help.is_empty() {
+        format!("--{}", flag.name)
+    } else {
+        format!("{} (--{})", flag.help, flag.name)
+    }
+}
+
+/// Ask `y/N` for a boolean flag.
+fn ask_confirm(flag: &FlagDef, io: &mut dyn PromptIo) -> KoralResult<Option<bool>> {
+    ask_yes_no(&format!("{}?", label(flag)), io)
+}
+
+/// Ask a `y/N` question; anything but yes is no.
+pub(crate) fn ask_yes_no(question: &str, io: &mut dyn PromptIo) -> KoralResult<Option<bool>> {
+    io.write(&format!("{} [y/N] ", question))?;
+    Ok(io
+        .read_line()?
+        .map(|answer| matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")))
+}
+
+/// Offer the possible values as a numbered menu, accepting a number or a value.
+fn ask_select(flag: &FlagDef, io: &mut dyn PromptIo) -> KoralResult<Option<String>> {
+    let mut menu = format!("{}:\n", label(flag));
+    for (i, value) in flag.possible_values.iter().enumerate() {
+        menu.push_str(&format!("  {}) {}\n", i + 1, value));
+    }
+    io.write(&menu)?;
+    loop {
+        io.write(&format!("Select 1-{}: ", flag.possible_values.len()))?;
+        let Some(answer) = io.read_line()? else {
+            return Ok(None);
+        };
+        let answer = answer.trim();
+        let chosen = match answer.parse::<usize>() {
+            Ok(n) => n.checked_sub(1).and_then(|i| flag.possible_values.get(i)),
+            Err(_) => flag
+                .possible_values
+                .iter()
+                .find(|p| p.eq_ignore_ascii_case(answer)),
+        };
+        match chosen.map(|value| (value, invalid(flag, value))) {
+            Some((value, None)) => return Ok(Some(value.clone())),
+            Some((_, Some(e))) => io.write(&format!("Invalid value: {}\n", e))?,
+            None => io.write(&format!("'{}' is not one of the choices\n", answer))?,
+        }
+    }
+}
+
+/// Ask for a value until a valid one is given, hiding sensitive input.
+fn ask_value(flag: &FlagDef, io: &mut dyn PromptIo) -> KoralResult<Option<String>> {
+    loop {
+        io.write(&format!("{}: ", label(flag)))?;
+        let answer = if flag.sensitive {
+            io.read_secret()?
+        } else {
+            io.read_line()?
+        };
+        let Some(answer) = answer else {
+            return Ok(None);
+        };
+        if answer.is_empty() {
+            continue;
+        }
+        match invalid(flag, &answer) {
+            Some(e) => io.write(&format!("Invalid value: {}\n", e))?,
+            None => return Ok(Some(answer)),
+        }
+    }
+}
+
+/// Why an answer is rejected: it does not convert to the flag's type or fails its validator.
+fn invalid(flag: &FlagDef, answer: &str) -> Option<String> {
+    let values = flag.split_value(answer);
+    let values: Vec<&str> = values.iter().map(|v| v.as_str()).collect();
+    if let Some(Err(e)) = flag.check.map(|check| check(&values)) {
+        return Some(e);
+    }
+    flag.validator
+        .and_then(|validator| values.iter().find_map(|v| validator(v).err()))
+}
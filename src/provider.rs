@@ -1,30 +1,458 @@
 use crate::flag::FlagDef;
+use std::fmt;
+
+/// Where the value of a flag came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValueSource {
+    /// Given on the command line
+    CommandLine,
+    /// Read from the named environment variable
+    Env(String),
+    /// Read from the named variable of a `.env` file
+    Dotenv(String),
+    /// Read from a configuration file (`None` when parsed from memory)
+    ConfigFile(Option<std::path::PathBuf>),
+    /// Supplied by a custom provider, identified by name
+    Provider(String),
+    /// Taken from the flag's default value
+    Default,
+    /// Typed in answer to a prompt
+    Prompt,
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::CommandLine => write!(f, "command line"),
+            ValueSource::Env(var) => write!(f, "env {}", var),
+            ValueSource::Dotenv(var) => write!(f, "dotenv {}", var),
+            ValueSource::ConfigFile(Some(path)) => write!(f, "config {}", path.display()),
+            ValueSource::ConfigFile(None) => write!(f, "config"),
+            ValueSource::Provider(name) => write!(f, "{}", name),
+            ValueSource::Default => write!(f, "default"),
+            ValueSource::Prompt => write!(f, "prompt"),
+        }
+    }
+}
 
 /// Trait for providing values for flags from various sources.
 pub trait ValueProvider {
     /// Try to get a value for the given flag.
     fn get_value(&self, flag: &FlagDef) -> Option<String>;
+
+    /// Try to get every value for the given flag, for sources holding lists.
+    fn get_values(&self, flag: &FlagDef) -> Option<Vec<String>> {
+        self.get_value(flag).map(|v| vec![v])
+    }
+
+    /// Get every value for the given flag, failing when the source holds one it cannot read.
+    ///
+    /// This is what the parser calls. Defaults to `get_values`, which never fails.
+    fn try_get_values(&self, flag: &FlagDef) -> crate::KoralResult<Option<Vec<String>>> {
+        Ok(self.get_values(flag))
+    }
+
+    /// Describe where a value returned for `flag` came from.
+    ///
+    /// Defaults to `ValueSource::Provider` with the provider's type name.
+    fn source(&self, flag: &FlagDef) -> ValueSource {
+        let _ = flag;
+        let name = std::any::type_name::<Self>();
+        ValueSource::Provider(name.rsplit("::").next().unwrap_or(name).to_string())
+    }
+}
+
+impl<P: ValueProvider + ?Sized> ValueProvider for std::sync::Arc<P> {
+    fn get_value(&self, flag: &FlagDef) -> Option<String> {
+        (**self).get_value(flag)
+    }
+
+    fn get_values(&self, flag: &FlagDef) -> Option<Vec<String>> {
+        (**self).get_values(flag)
+    }
+
+    fn try_get_values(&self, flag: &FlagDef) -> crate::KoralResult<Option<Vec<String>>> {
+        (**self).try_get_values(flag)
+    }
+
+    fn source(&self, flag: &FlagDef) -> ValueSource {
+        (**self).source(flag)
+    }
+}
+
+/// The providers used when an application does not choose its own.
+pub fn default_providers() -> Vec<Box<dyn ValueProvider>> {
+    vec![Box::new(EnvProvider)]
+}
+
+/// Providers a command hands down to subcommands that do not choose their own.
+#[doc(hidden)]
+#[derive(Clone, Default)]
+pub struct ProviderChain(Vec<std::sync::Arc<dyn ValueProvider>>);
+
+impl ProviderChain {
+    /// Share `providers` with the subcommands.
+    pub fn new(providers: Vec<Box<dyn ValueProvider>>) -> Self {
+        Self(providers.into_iter().map(std::sync::Arc::from).collect())
+    }
+
+    /// The providers, for one more parser.
+    pub fn providers(&self) -> Vec<Box<dyn ValueProvider>> {
+        self.0
+            .iter()
+            .map(|p| Box::new(p.clone()) as Box<dyn ValueProvider>)
+            .collect()
+    }
+}
+
+impl fmt::Debug for ProviderChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ProviderChain({} providers)", self.0.len())
+    }
 }
 
 /// Provides values from environment variables.
+///
+/// When `TOKEN` is unset, `TOKEN_FILE` may name a file holding the value,
+/// as commonly done for container secrets. A file that cannot be read is an error.
+#[derive(Clone, Copy, Debug, Default)]
 pub struct EnvProvider;
 
+impl EnvProvider {
+    fn read(&self, flag: &FlagDef) -> crate::KoralResult<Option<String>> {
+        let Some(env_var) = &flag.env else {
+            return Ok(None);
+        };
+        if let Some(val) = crate::host::var(env_var) {
+            return Ok(Some(val));
+        }
+        let file_var = format!("{}_FILE", env_var);
+        match crate::host::var(&file_var) {
+            Some(path) => std::fs::read_to_string(crate::host::resolve(&path))
+                .map(|content| Some(content.trim().to_string()))
+                .map_err(|e| {
+                    crate::KoralError::IoError(format!(
+                        "Failed to read {} named by {}: {}",
+                        path, file_var, e
+                    ))
+                }),
+            None => Ok(None),
+        }
+    }
+}
+
 impl ValueProvider for EnvProvider {
     fn get_value(&self, flag: &FlagDef) -> Option<String> {
-        if let Some(env_var) = &flag.env {
-            if let Ok(val) = std::env::var(env_var) {
-                return Some(val);
-            }
+        self.read(flag).ok().flatten()
+    }
+
+    fn try_get_values(&self, flag: &FlagDef) -> crate::KoralResult<Option<Vec<String>>> {
+        Ok(self.read(flag)?.map(|v| vec![v]))
+    }
+
+    fn source(&self, flag: &FlagDef) -> ValueSource {
+        let env_var = flag.env.clone().unwrap_or_default();
+        if crate::host::var_os(&env_var).is_none()
+            && crate::host::var_os(&format!("{}_FILE", env_var)).is_some()
+        {
+            return ValueSource::Env(format!("{}_FILE", env_var));
         }
-        None
+        ValueSource::Env(env_var)
     }
 }
 
 /// Provides values from default values defined in flags.
+#[derive(Clone, Copy, Debug, Default)]
 pub struct DefaultProvider;
 
 impl ValueProvider for DefaultProvider {
     fn get_value(&self, flag: &FlagDef) -> Option<String> {
         flag.default_value.clone()
     }
+
+    fn source(&self, _flag: &FlagDef) -> ValueSource {
+        ValueSource::Default
+    }
+}
+
+/// Provides values from a configuration file (TOML, JSON or INI).
+///
+/// Top-level keys map to flag names (`dry_run` also matches `--dry-run`).
+/// A `[name]` section holds values for the subcommand of that name and
+/// takes precedence over top-level keys; nested commands use dotted
+/// sections such as `[k8s.clusters]`.
+#[derive(Clone, Debug)]
+pub struct FileProvider {
+    path: Option<std::path::PathBuf>,
+    values: std::sync::Arc<crate::config::ConfigValues>,
+    section: Option<String>,
+}
+
+impl FileProvider {
+    /// Load a configuration file, detecting the format from its extension.
+    pub fn load(path: impl AsRef<std::path::Path>) -> crate::KoralResult<Self> {
+        let path = path.as_ref();
+        let format = crate::config::ConfigFormat::from_path(path).ok_or_else(|| {
+            crate::KoralError::Validation(format!(
+                "Unsupported config file format: {}",
+                path.display()
+            ))
+        })?;
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            crate::KoralError::IoError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let mut provider = Self::parse(&content, format)?;
+        provider.path = Some(path.to_path_buf());
+        Ok(provider)
+    }
+
+    /// Create a provider from configuration content.
+    pub fn parse(content: &str, format: crate::config::ConfigFormat) -> crate::KoralResult<Self> {
+        Ok(Self {
+            path: None,
+            values: std::sync::Arc::new(crate::config::parse(content, format)?),
+            section: None,
+        })
+    }
+
+    /// Find and load the configuration file of an application.
+    ///
+    /// The file is chosen by `--config <path>` in `args`, the `<NAME>_CONFIG`
+    /// environment variable, or `config.{toml,json,ini}` in the XDG config
+    /// directory, in that order. Returns `None` when no file is configured.
+    pub fn discover(app_name: &str, args: &[String]) -> crate::KoralResult<Option<Self>> {
+        crate::config::locate(app_name, args)
+            .map(Self::load)
+            .transpose()
+    }
+
+    /// Scope lookups to a subcommand section, falling back to top-level keys.
+    pub fn section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+
+    /// Path of the loaded file, if it was read from disk.
+    pub fn path(&self) -> Option<&std::path::Path> {
+        self.path.as_deref()
+    }
+
+    /// The `--config <FILE>` flag selecting the configuration file.
+    pub fn config_flag() -> FlagDef {
+        FlagDef {
+            name: "config".to_string(),
+            long: Some("config".to_string()),
+            short: None,
+            help: "Path to the configuration file".to_string(),
+            takes_value: true,
+            required: false,
+            default_value: None,
+            env: None,
+            validator: None,
+            aliases: vec![],
+            value_name: Some("FILE".to_string()),
+            help_heading: None,
+            multiple: false,
+            value_delimiter: None,
+            count: false,
+            negatable: false,
+            missing_value: None,
+            conflicts_with: vec![],
+            requires: vec![],
+            required_if_eq: vec![],
+            required_unless_present: vec![],
+            default_value_if: vec![],
+            possible_values: vec![],
+            from_file: false,
+            sensitive: false,
+            global: false,
+            check: None,
+        }
+    }
+
+    /// Command aliases defined in the `[alias]` table, sorted by name.
+    ///
+    /// A string is split into arguments like a shell command line;
+    /// an array lists the arguments one by one.
+    pub fn aliases(&self) -> crate::KoralResult<Vec<(String, Vec<String>)>> {
+        let mut aliases = Vec::new();
+        for (key, values) in self.values.iter() {
+            let Some(name) = key.strip_prefix("alias.") else {
+                continue;
+            };
+            let expansion = match values.as_slice() {
+                [line] => crate::response::split_words(line).ok_or_else(|| {
+                    crate::KoralError::Validation(format!(
+                        "Unterminated quote in alias '{}': {}",
+                        name, line
+                    ))
+                })?,
+                _ => values.clone(),
+            };
+            aliases.push((name.to_string(), expansion));
+        }
+        aliases.sort();
+        Ok(aliases)
+    }
+
+    fn lookup(&self, key: &str) -> Option<&Vec<String>> {
+        let scoped = self
+            .section
+            .as_ref()
+            .and_then(|s| self.values.get(&format!("{}.{}", s, key)));
+        scoped.or_else(|| self.values.get(key))
+    }
+}
+
+impl ValueProvider for FileProvider {
+    fn get_value(&self, flag: &FlagDef) -> Option<String> {
+        self.get_values(flag).and_then(|v| v.last().cloned())
+    }
+
+    fn get_values(&self, flag: &FlagDef) -> Option<Vec<String>> {
+        self.lookup(&flag.name)
+            .or_else(|| self.lookup(&flag.name.replace('-', "_")))
+            .cloned()
+    }
+
+    fn source(&self, _flag: &FlagDef) -> ValueSource {
+        ValueSource::ConfigFile(self.path.clone())
+    }
+}
+
+/// Provides `env` values from a `.env` file without touching the process environment.
+///
+/// Lines hold `KEY=value` pairs and may start with `export`. Values may be
+/// wrapped in single or double quotes; `#` starts a comment outside quotes.
+#[derive(Clone, Debug, Default)]
+pub struct DotenvProvider {
+    path: Option<std::path::PathBuf>,
+    vars: std::sync::Arc<std::collections::HashMap<String, String>>,
+}
+
+impl DotenvProvider {
+    /// Load a `.env` file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> crate::KoralResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            crate::KoralError::IoError(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let mut provider = Self::parse(&content);
+        provider.path = Some(path.to_path_buf());
+        Ok(provider)
+    }
+
+    /// Create a provider from `.env` content.
+    pub fn parse(content: &str) -> Self {
+        Self {
+            path: None,
+            vars: std::sync::Arc::new(parse_dotenv(content)),
+        }
+    }
+
+    /// Find and load a dotenv file.
+    ///
+    /// The `KORAL_DOTENV` environment variable names the file explicitly;
+    /// otherwise `file_name` is searched for in the current directory and
+    /// its parents. Returns `None` when no file is found.
+    pub fn discover(file_name: &str) -> crate::KoralResult<Option<Self>> {
+        if let Some(path) = crate::host::var_os("KORAL_DOTENV").filter(|v| !v.is_empty()) {
+            return Self::load(crate::host::resolve(path)).map(Some);
+        }
+        let Some(cwd) = crate::host::current_dir() else {
+            return Ok(None);
+        };
+        cwd.ancestors()
+            .map(|dir| dir.join(file_name))
+            .find(|p| p.is_file())
+            .map(Self::load)
+            .transpose()
+    }
+
+    /// Path of the loaded file, if it was read from disk.
+    pub fn path(&self) -> Option<&std::path::Path> {
+        self.path.as_deref()
+    }
+
+    /// Value of a variable defined in the file.
+    pub fn var(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+}
+
+impl ValueProvider for DotenvProvider {
+    fn get_value(&self, flag: &FlagDef) -> Option<String> {
+        flag.env
+            .as_deref()
+            .and_then(|env| self.var(env))
+            .map(str::to_string)
+    }
+
+    fn source(&self, flag: &FlagDef) -> ValueSource {
+        ValueSource::Dotenv(flag.env.clone().unwrap_or_default())
+    }
+}
+
+fn parse_dotenv(content: &str) -> std::collections::HashMap<String, String> {
+    let mut vars = std::collections::HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map_or(line, str::trim_start);
+        let Some((key, raw)) = line.split_once('=') else {
+            continue;
+        };
+        let raw = raw.trim_start();
+        let value = if let Some(rest) = raw.strip_prefix('"') {
+            // Double quotes support escapes and end at the closing quote
+            let mut value = String::new();
+            let mut chars = rest.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some(other) => value.push(other),
+                        None => value.push('\\'),
+                    },
+                    c => value.push(c),
+                }
+            }
+            value
+        } else if let Some(rest) = raw.strip_prefix('\'') {
+            rest.split('\'').next().unwrap_or_default().to_string()
+        } else {
+            // Unquoted values end at a comment preceded by whitespace
+            let end = raw
+                .char_indices()
+                .find(|&(i, c)| c == '#' && raw[..i].ends_with(char::is_whitespace))
+                .map_or(raw.len(), |(i, _)| i);
+            raw[..end].trim_end().to_string()
+        };
+        vars.insert(key.trim().to_string(), value);
+    }
+    vars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let vars = parse_dotenv(
+            "# comment\nexport API_TOKEN=abc123\nNAME = \"a \\\"b\\\" # c\"  # trailing\nRAW='x # y'\nPLAIN=v # note\nexporter=1\n",
+        );
+        assert_eq!(vars["API_TOKEN"], "abc123");
+        assert_eq!(vars["NAME"], "a \"b\" # c");
+        assert_eq!(vars["RAW"], "x # y");
+        assert_eq!(vars["PLAIN"], "v");
+        assert_eq!(vars["exporter"], "1");
+    }
 }
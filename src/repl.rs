@@ -0,0 +1,346 @@
+use crate::command::CommandDef;
+use crate::error::KoralResult;
+use crate::output::Terminal;
+use crate::traits::App;
+use std::any::Any;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Commands the session handles itself.
+const BUILTINS: [&str; 3] = ["exit", "quit", "help"];
+
+/// Source of the lines typed in a REPL session.
+pub trait LineReader {
+    /// Read a line after showing `prompt`; `None` ends the session.
+    fn read_line(&mut self, prompt: &str) -> KoralResult<Option<String>>;
+
+    /// Remember a line for history navigation.
+    fn add_history(&mut self, line: &str) {
+        let _ = line;
+    }
+}
+
+/// Completes command names and flags of an application's command tree.
+#[derive(Debug, Clone)]
+pub struct CommandCompleter {
+    tree: CommandDef,
+}
+
+impl CommandCompleter {
+    /// Create a completer for the commands of `app`.
+    pub fn new<A: App + ?Sized>(app: &A) -> Self {
+        let mut tree = crate::completion::command_tree(app);
+        tree.subcommands.retain(|s| s.name != SHELL_COMMAND);
+        Self { tree }
+    }
+
+    /// Complete the word ending at byte `pos` of `line`.
+    ///
+    /// Returns where the word starts and the candidates replacing it.
+    pub fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map_or(0, |i| i + 1);
+        let (done, partial) = line.split_at(start);
+        let words = crate::response::split_words(done).unwrap_or_default();
+
+        let mut cmd = &self.tree;
+        for word in &words {
+            if let Some(sub) = cmd
+                .subcommands
+                .iter()
+                .find(|s| &s.name == word || s.aliases.contains(word))
+            {
+                cmd = sub;
+            }
+        }
+
+        let mut candidates: Vec<String> = if partial.starts_with('-') {
+            let mut flags = vec!["--help".to_string()];
+            for flag in &cmd.flags {
+                let long = flag.long.as_deref().unwrap_or(&flag.name);
+                flags.push(format!("--{}", long));
+                if flag.negatable {
+                    flags.push(format!("--no-{}", long));
+                }
+            }
+            flags
+        } else {
+            let mut names: Vec<String> = cmd.subcommands.iter().map(|s| s.name.clone()).collect();
+            if words.is_empty() {
+                names.extend(BUILTINS.iter().map(|b| b.to_string()));
+            }
+            names
+        };
+        candidates.retain(|c| c.starts_with(partial));
+        candidates.sort();
+        candidates.dedup();
+        (start, candidates)
+    }
+}
+
+/// Name of the subcommand `#[app(repl)]` adds to start a session.
+pub const SHELL_COMMAND: &str = "shell";
+
+/// The `shell` subcommand added by `#[app(repl)]`.
+///
+/// Its session only edits lines, keeps history and completes with the `repl` feature.
+#[doc(hidden)]
+pub fn shell_command() -> CommandDef {
+    CommandDef::new(SHELL_COMMAND, "Start an interactive session")
+}
+
+/// An interactive session running the commands of an application line by line.
+///
+/// Lines are split like a shell command line and run as arguments of the
+/// application, so shared state persists from one command to the next.
+/// `help [command]` shows help and `exit` (or `quit`) ends the session.
+pub struct Repl<'a, A: App + ?Sized> {
+    app: &'a mut A,
+    prompt: String,
+    reader: Option<Box<dyn LineReader + 'a>>,
+    history_file: Option<PathBuf>,
+    terminal: Option<Terminal>,
+}
+
+impl<'a, A: App + ?Sized> Repl<'a, A> {
+    /// Create a session for `app`, prompting with `<name>> `.
+    ///
+    /// With the `repl` feature, history is kept in `$XDG_STATE_HOME/<name>/history`
+    /// (or `~/.local/state/<name>/history`).
+    pub fn new(app: &'a mut A) -> Self {
+        let prompt = format!("{}> ", app.name());
+        let history_file = default_history_file(app.name());
+        Self {
+            app,
+            prompt,
+            reader: None,
+            history_file,
+            terminal: None,
+        }
+    }
+
+    /// Set the prompt shown before each line.
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Read lines from `reader` instead of the terminal.
+    pub fn reader<R: LineReader + 'a>(mut self, reader: R) -> Self {
+        self.reader = Some(Box::new(reader));
+        self
+    }
+
+    /// Run the commands in `terminal` instead of the process's stdio.
+    pub fn terminal(mut self, terminal: Terminal) -> Self {
+        self.terminal = Some(terminal);
+        self
+    }
+
+    /// Set the file history is loaded from and saved to; `None` disables it.
+    pub fn history_file(mut self, path: Option<PathBuf>) -> Self {
+        self.history_file = path;
+        self
+    }
+
+    /// Run the session until `exit` or the end of input.
+    pub fn run(self) -> KoralResult<()> {
+        self.run_nested(None)
+    }
+
+    /// Run the session with a shared state passed to every command.
+    pub fn run_with_state(self, state: &mut dyn Any) -> KoralResult<()> {
+        self.run_nested(Some(state))
+    }
+
+    #[doc(hidden)]
+    pub fn run_nested(self, mut state: Option<&mut dyn Any>) -> KoralResult<()> {
+        let Repl {
+            app,
+            prompt,
+            reader,
+            history_file,
+            terminal,
+        } = self;
+        let terminal = terminal.unwrap_or_default();
+        let mut reader = match reader {
+            Some(reader) => reader,
+            None => default_reader(CommandCompleter::new(&*app), history_file, &terminal)?,
+        };
+        let name = app.name().to_string();
+
+        while let Some(line) = reader.read_line(&prompt)? {
+            let Some(words) = crate::response::split_words(&line) else {
+                terminal.print_error(&"unterminated quote")?;
+                continue;
+            };
+            let Some(first) = words.first() else {
+                continue;
+            };
+            reader.add_history(line.trim());
+
+            let mut args = vec![name.clone()];
+            match first.as_str() {
+                "exit" | "quit" => break,
+                "help" => {
+                    args.extend(words[1..].iter().cloned());
+                    args.push("--help".to_string());
+                }
+                SHELL_COMMAND => {
+                    writeln!(terminal.stderr(), "Already in an interactive session")?;
+                    continue;
+                }
+                _ => args.extend(words),
+            }
+            let inherited = crate::traits::Inherited {
+                terminal: Some(terminal.clone()),
+                ..Default::default()
+            };
+            if let Err(e) = app.run_nested(state.as_deref_mut(), args, inherited) {
+                terminal.print_error(&e)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_history_file(app_name: &str) -> Option<PathBuf> {
+    let base = crate::host::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| crate::host::var_os("HOME").map(|h| PathBuf::from(h).join(".local/state")))?;
+    Some(base.join(app_name).join("history"))
+}
+
+#[cfg(feature = "repl")]
+fn default_reader<'a>(
+    completer: CommandCompleter,
+    history_file: Option<PathBuf>,
+    _terminal: &Terminal,
+) -> KoralResult<Box<dyn LineReader + 'a>> {
+    Ok(Box::new(editor::EditorReader::new(
+        completer,
+        history_file,
+    )?))
+}
+
+#[cfg(not(feature = "repl"))]
+fn default_reader<'a>(
+    _completer: CommandCompleter,
+    _history_file: Option<PathBuf>,
+    terminal: &Terminal,
+) -> KoralResult<Box<dyn LineReader + 'a>> {
+    Ok(Box::new(StdinReader::new(terminal.clone())))
+}
+
+/// Reads plain lines, without editing, history or completion.
+///
+/// The prompt goes to the terminal's stdout and lines come from its input,
+/// the process's stdin unless replaced.
+#[derive(Debug, Clone, Default)]
+pub struct StdinReader {
+    terminal: Terminal,
+}
+
+impl StdinReader {
+    /// Read lines in `terminal`.
+    pub fn new(terminal: Terminal) -> Self {
+        Self { terminal }
+    }
+}
+
+impl LineReader for StdinReader {
+    fn read_line(&mut self, prompt: &str) -> KoralResult<Option<String>> {
+        self.terminal.print(prompt)?;
+        self.terminal.input().borrow_mut().read_line()
+    }
+}
+
+#[cfg(feature = "repl")]
+mod editor {
+    use super::{CommandCompleter, LineReader};
+    use crate::error::{KoralError, KoralResult};
+    use rustyline::completion::Completer;
+    use rustyline::error::ReadlineError;
+    use rustyline::history::DefaultHistory;
+    use rustyline::Editor;
+    use std::path::PathBuf;
+
+    pub(super) struct EditorHelper(CommandCompleter);
+
+    impl rustyline::Helper for EditorHelper {}
+    impl rustyline::highlight::Highlighter for EditorHelper {}
+    impl rustyline::validate::Validator for EditorHelper {}
+
+    impl rustyline::hint::Hinter for EditorHelper {
+        type Hint = String;
+    }
+
+    impl Completer for EditorHelper {
+        type Candidate = String;
+
+        fn complete(
+            &self,
+            line: &str,
+            pos: usize,
+            _ctx: &rustyline::Context<'_>,
+        ) -> rustyline::Result<(usize, Vec<String>)> {
+            Ok(self.0.complete(line, pos))
+        }
+    }
+
+    /// Terminal line editor with history saved to a file.
+    pub(super) struct EditorReader {
+        editor: Editor<EditorHelper, DefaultHistory>,
+        history_file: Option<PathBuf>,
+    }
+
+    impl EditorReader {
+        pub(super) fn new(
+            completer: CommandCompleter,
+            history_file: Option<PathBuf>,
+        ) -> KoralResult<Self> {
+            let mut editor = Editor::new().map_err(readline_error)?;
+            editor.set_helper(Some(EditorHelper(completer)));
+            if let Some(path) = &history_file {
+                // A missing history file is normal on first use
+                let _ = editor.load_history(path);
+            }
+            Ok(Self {
+                editor,
+                history_file,
+            })
+        }
+    }
+
+    impl LineReader for EditorReader {
+        fn read_line(&mut self, prompt: &str) -> KoralResult<Option<String>> {
+            match self.editor.readline(prompt) {
+                Ok(line) => Ok(Some(line)),
+                // Ctrl-C abandons the current line only
+                Err(ReadlineError::Interrupted) => Ok(Some(String::new())),
+                Err(ReadlineError::Eof) => Ok(None),
+                Err(e) => Err(readline_error(e)),
+            }
+        }
+
+        fn add_history(&mut self, line: &str) {
+            let _ = self.editor.add_history_entry(line);
+        }
+    }
+
+    impl Drop for EditorReader {
+        fn drop(&mut self) {
+            if let Some(path) = &self.history_file {
+                if let Some(dir) = path.parent() {
+                    let _ = std::fs::create_dir_all(dir);
+                }
+                let _ = self.editor.save_history(path);
+            }
+        }
+    }
+
+    fn readline_error(e: ReadlineError) -> KoralError {
+        KoralError::IoError(e.to_string())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::{KoralError, KoralResult};
+
+/// How deeply response files may include other response files.
+const MAX_DEPTH: usize = 10;
+
+/// Expand `@file` arguments into the arguments listed in that file.
+///
+/// Files hold shell-quoted arguments, typically one per line; lines starting
+/// with `#` are comments. `@@arg` passes `@arg` through literally, and
+/// arguments after `--` are never expanded.
+pub(crate) fn expand(args: &[String]) -> KoralResult<Vec<String>> {
+    let mut out = Vec::with_capacity(args.len());
+    expand_into(args, 0, &mut out)?;
+    Ok(out)
+}
+
+fn expand_into(args: &[String], depth: usize, out: &mut Vec<String>) -> KoralResult<()> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            out.push(arg.clone());
+            out.extend(iter.cloned());
+            break;
+        }
+        if let Some(escaped) = arg.strip_prefix("@@") {
+            out.push(format!("@{}", escaped));
+        } else if let Some(path) = arg.strip_prefix('@').filter(|p| !p.is_empty()) {
+            if depth >= MAX_DEPTH {
+                return Err(KoralError::Validation(format!(
+                    "Response file '{}' is nested more than {} levels deep",
+                    path, MAX_DEPTH
+                )));
+            }
+            let content = std::fs::read_to_string(crate::host::resolve(path)).map_err(|e| {
+                KoralError::IoError(format!("Failed to read response file {}: {}", path, e))
+            })?;
+            expand_into(&split(&content)?, depth + 1, out)?;
+        } else {
+            out.push(arg.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Split response file content into arguments, honouring quotes and escapes.
+fn split(content: &str) -> KoralResult<Vec<String>> {
+    let mut args = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let words = split_words(line).ok_or_else(|| {
+            KoralError::Validation(format!(
+                "Unterminated quote in response file line: {}",
+                line
+            ))
+        })?;
+        args.extend(words);
+    }
+    Ok(args)
+}
+
+/// Split a command line into words, honouring quotes and escapes.
+///
+/// Returns `None` when a quote is left open.
+pub(crate) fn split_words(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"') | (None, '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return None;
+    }
+    if in_word {
+        words.push(current);
+    }
+    Some(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_quoted_lines() {
+        let args =
+            split("# comment\n--name \"John Doe\"\n'--tag=a b' plain\\ word\n\"\"\n").unwrap();
+        assert_eq!(
+            args,
+            vec!["--name", "John Doe", "--tag=a b", "plain word", ""]
+        );
+    }
+
+    #[test]
+    fn test_unterminated_quote() {
+        assert!(split("--name \"oops\n").is_err());
+    }
+}
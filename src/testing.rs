@@ -0,0 +1,315 @@
+use crate::error::{KoralError, KoralResult};
+use crate::host::{self, Fake};
+use crate::output::{run_with_terminal, Buffer, Output, Terminal};
+use crate::prompt::PromptIo;
+use crate::traits::App;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Environment variable that makes snapshot assertions rewrite their files.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "KORAL_UPDATE_SNAPSHOTS";
+
+/// Run `app` with `args`, the program name first, in an empty environment.
+///
+/// Shorthand for `Harness::new().run(app, args)`.
+pub fn run<A, I, S>(app: &mut A, args: I) -> TestOutcome
+where
+    A: App + ?Sized,
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    Harness::new().run(app, args)
+}
+
+/// Runs apps in-process, capturing their output.
+///
+/// The app sees only the environment variables, working directory and stdin
+/// given here; the process's own are neither read nor changed. Without a
+/// terminal to answer them, prompts are skipped unless `interactive` is set.
+#[derive(Debug, Clone, Default)]
+pub struct Harness {
+    vars: HashMap<String, String>,
+    cwd: Option<PathBuf>,
+    stdin: String,
+    interactive: bool,
+    color: bool,
+    width: Option<usize>,
+}
+
+impl Harness {
+    /// An empty environment, no input and no terminal.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set an environment variable.
+    pub fn env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// Run in `dir`; relative paths, `.env` and `@file` values are looked up there.
+    pub fn cwd(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(dir.into());
+        self
+    }
+
+    /// Look for `<app>/config.{toml,json,ini}` under `dir`, by setting `XDG_CONFIG_HOME`.
+    pub fn config_dir(self, dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref().to_string_lossy().into_owned();
+        self.env("XDG_CONFIG_HOME", dir)
+    }
+
+    /// Feed `input` to the app, for `-` values and prompt answers alike.
+    pub fn stdin(mut self, input: impl Into<String>) -> Self {
+        self.stdin = input.into();
+        self
+    }
+
+    /// Behave as if run from a terminal, so prompts and confirmations are asked.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Keep ANSI styles in the captured output.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Wrap help to `width` columns instead of 100.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Run `app` with `args`, the program name first.
+    pub fn run<A, I, S>(&self, app: &mut A, args: I) -> TestOutcome
+    where
+        A: App + ?Sized,
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.execute(app, None, args.into_iter().map(Into::into).collect())
+    }
+
+    /// Run `app` with `args` and shared `state`, as `App::run_with_state` would.
+    pub fn run_with_state<A, I, S>(&self, app: &mut A, state: &mut dyn Any, args: I) -> TestOutcome
+    where
+        A: App + ?Sized,
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.execute(app, Some(state), args.into_iter().map(Into::into).collect())
+    }
+
+    fn execute<A: App + ?Sized>(
+        &self,
+        app: &mut A,
+        state: Option<&mut dyn Any>,
+        args: Vec<String>,
+    ) -> TestOutcome {
+        let stdout = Buffer::new();
+        let stderr = Buffer::new();
+        let stdin = Rc::new(RefCell::new(self.stdin.clone()));
+        let input = ScriptedInput {
+            stdin: stdin.clone(),
+            stderr: Output::new(stderr.clone()),
+            interactive: self.interactive,
+        };
+        let mut terminal = Terminal::new(stdout.clone(), stderr.clone())
+            .with_color(self.color)
+            .with_input(input);
+        if let Some(width) = self.width {
+            terminal = terminal.with_width(width);
+        }
+        let fake = Fake {
+            vars: self.vars.clone(),
+            cwd: self.cwd.clone(),
+            stdin,
+            extensions: HashMap::new(),
+        };
+        let (result, fake) = host::with_fake(fake, || {
+            run_with_terminal(app, terminal.clone(), state, args)
+        });
+        if let Err(e) = &result {
+            let _ = terminal.print_error(e);
+        }
+        TestOutcome {
+            stdout: stdout.contents(),
+            stderr: stderr.contents(),
+            exit_code: result.as_ref().map_or_else(KoralError::exit_code, |_| 0),
+            result,
+            extensions: fake.extensions,
+        }
+    }
+}
+
+/// Answers prompts from the harness's stdin, asking on the captured stderr.
+struct ScriptedInput {
+    stdin: Rc<RefCell<String>>,
+    stderr: Output,
+    interactive: bool,
+}
+
+impl PromptIo for ScriptedInput {
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    fn write(&mut self, text: &str) -> KoralResult<()> {
+        self.stderr.write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> KoralResult<Option<String>> {
+        let mut stdin = self.stdin.borrow_mut();
+        if stdin.is_empty() {
+            return Ok(None);
+        }
+        let end = stdin.find('\n').map_or(stdin.len(), |i| i + 1);
+        let line: String = stdin.drain(..end).collect();
+        Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+    }
+}
+
+/// What an app run by the harness did.
+pub struct TestOutcome {
+    /// Everything written to stdout.
+    pub stdout: String,
+    /// Everything written to stderr, including prompts and the reported error.
+    pub stderr: String,
+    /// Status the process would exit with; see `KoralError::exit_code`.
+    pub exit_code: i32,
+    /// What the run returned.
+    pub result: KoralResult<()>,
+    /// Extensions left in the context of the handler that ran, when it takes extractors.
+    pub extensions: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl TestOutcome {
+    /// The extension of type `T`, if one was left.
+    pub fn extension<T: Any>(&self) -> Option<&T> {
+        self.extensions
+            .get(&TypeId::of::<T>())
+            .and_then(|v| v.downcast_ref())
+    }
+
+    /// Panic unless the run succeeded.
+    #[track_caller]
+    pub fn assert_success(&self) -> &Self {
+        if let Err(e) = &self.result {
+            panic!("expected success, got: {}\n{}", e, self.dump());
+        }
+        self
+    }
+
+    /// Panic unless the run failed.
+    #[track_caller]
+    pub fn assert_failure(&self) -> &Self {
+        if self.result.is_ok() {
+            panic!("expected failure, got success\n{}", self.dump());
+        }
+        self
+    }
+
+    /// Panic unless the run would exit with `code`.
+    #[track_caller]
+    pub fn assert_exit_code(&self, code: i32) -> &Self {
+        if self.exit_code != code {
+            panic!(
+                "expected exit code {}, got {}\n{}",
+                code,
+                self.exit_code,
+                self.dump()
+            );
+        }
+        self
+    }
+
+    /// Panic unless stdout contains `needle`.
+    #[track_caller]
+    pub fn assert_stdout_contains(&self, needle: &str) -> &Self {
+        if !self.stdout.contains(needle) {
+            panic!("stdout does not contain {:?}\n{}", needle, self.dump());
+        }
+        self
+    }
+
+    /// Panic unless stderr contains `needle`.
+    #[track_caller]
+    pub fn assert_stderr_contains(&self, needle: &str) -> &Self {
+        if !self.stderr.contains(needle) {
+            panic!("stderr does not contain {:?}\n{}", needle, self.dump());
+        }
+        self
+    }
+
+    /// Compare stdout, e.g. help text, with the snapshot at `path`; see `assert_snapshot`.
+    #[track_caller]
+    pub fn assert_stdout_snapshot(&self, path: impl AsRef<Path>) -> &Self {
+        assert_snapshot(path, &self.stdout);
+        self
+    }
+
+    fn dump(&self) -> String {
+        format!(
+            "--- stdout ---\n{}--- stderr ---\n{}",
+            self.stdout, self.stderr
+        )
+    }
+}
+
+impl std::fmt::Debug for TestOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestOutcome")
+            .field("stdout", &self.stdout)
+            .field("stderr", &self.stderr)
+            .field("exit_code", &self.exit_code)
+            .field("result", &self.result)
+            .field("extensions", &self.extensions.len())
+            .finish()
+    }
+}
+
+/// Panic unless `actual` matches the snapshot file at `path`.
+///
+/// While `KORAL_UPDATE_SNAPSHOTS` is set, the snapshot is written from `actual`
+/// instead, creating it if missing; otherwise a missing snapshot fails. Line
+/// endings are ignored when comparing.
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some_and(|v| !v.is_empty());
+    if update {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|e| panic!("cannot create {}: {}", dir.display(), e));
+        }
+        std::fs::write(path, actual)
+            .unwrap_or_else(|e| panic!("cannot write snapshot {}: {}", path.display(), e));
+        return;
+    }
+    let expected = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        panic!(
+            "cannot read snapshot {}: {}; set {}=1 to create it",
+            path.display(),
+            e,
+            UPDATE_SNAPSHOTS_ENV
+        )
+    });
+    if expected.replace("\r\n", "\n") != actual.replace("\r\n", "\n") {
+        panic!(
+            "snapshot {} does not match; set {}=1 to update it\n--- expected ---\n{}--- actual ---\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS_ENV,
+            expected,
+            actual
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::context::Context;
 use crate::error::KoralResult;
 use std::fmt::Display;
+use std::io::Write;
 use std::str::FromStr;
 
 /// Trait for types that can be parsed from command line arguments.
@@ -8,6 +9,30 @@ pub trait FlagValue: Clone + Send + Sync + ToString + FromStr + 'static
 where
     <Self as FromStr>::Err: Display,
 {
+    /// Parse the last of the collected values, as single-valued flags do.
+    fn from_last(values: &[&str]) -> Result<Self, String> {
+        match values.last() {
+            Some(s) => Self::from_str(s).map_err(|e| e.to_string()),
+            None => Err("No value provided".to_string()),
+        }
+    }
+
+    /// Parse the value of a flag taking none, e.g. a boolean switch.
+    /// Given without a value, the flag reads as `true`.
+    fn from_switch(values: &[&str]) -> Result<Self, String> {
+        match values {
+            [] => Self::from_str("true").map_err(|e| e.to_string()),
+            _ => Self::from_last(values),
+        }
+    }
+
+    /// Parse every collected value, as multi-valued flags do.
+    fn from_each(values: &[&str]) -> Result<Vec<Self>, String> {
+        values
+            .iter()
+            .map(|s| Self::from_str(s).map_err(|e| e.to_string()))
+            .collect()
+    }
 }
 
 impl<T> FlagValue for T
@@ -17,6 +42,46 @@ where
 {
 }
 
+/// Types accepting only a fixed set of values, e.g. enums deriving `FlagValue`.
+pub trait PossibleValues {
+    /// Every accepted value, as written on the command line.
+    fn possible_values() -> Vec<&'static str>;
+}
+
+/// Lookup of `PossibleValues` used by `#[derive(Flag)]`, which cannot tell
+/// whether a value type implements it.
+#[doc(hidden)]
+pub struct PossibleValuesProbe<T>(std::marker::PhantomData<T>);
+
+impl<T> PossibleValuesProbe<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaPossibleValues {
+    fn possible_values(&self) -> Vec<&'static str>;
+}
+
+impl<T: PossibleValues> ViaPossibleValues for PossibleValuesProbe<T> {
+    fn possible_values(&self) -> Vec<&'static str> {
+        T::possible_values()
+    }
+}
+
+#[doc(hidden)]
+pub trait ViaAnyValue {
+    fn possible_values(&self) -> Vec<&'static str>;
+}
+
+impl<T> ViaAnyValue for &PossibleValuesProbe<T> {
+    fn possible_values(&self) -> Vec<&'static str> {
+        vec![]
+    }
+}
+
 /// Trait for types that can be parsed from a list of arguments (e.g. subcommands).
 pub trait FromArgs: Sized {
     /// Parse from arguments.
@@ -27,6 +92,57 @@ pub trait FromArgs: Sized {
     }
 }
 
+/// What a command hands down to the subcommand it dispatches to.
+#[doc(hidden)]
+#[derive(Clone, Debug, Default)]
+pub struct Inherited {
+    /// Names of the subcommands leading to the receiving command.
+    pub path: Vec<String>,
+    /// Configuration file located by the root command.
+    pub config: Option<crate::provider::FileProvider>,
+    /// Dotenv file located by the root command.
+    pub dotenv: Option<crate::provider::DotenvProvider>,
+    /// Providers of the nearest ancestor that chose its own.
+    pub providers: Option<crate::provider::ProviderChain>,
+    /// Environment variable prefix for the receiving command's flags.
+    pub env_prefix: Option<String>,
+    /// Environment variable prefix of the root command, or its name.
+    pub root_env_prefix: Option<String>,
+    /// Whether an ancestor accepts prefixes of long flags.
+    pub infer_long_args: bool,
+    /// Whether an ancestor accepts prefixes of subcommand names.
+    pub infer_subcommands: bool,
+    /// Global flags declared by the ancestors.
+    pub globals: Vec<crate::flag::FlagDef>,
+    /// Values the parent parsed for those global flags.
+    pub global_values: GlobalValues,
+    /// Context of the dispatching command, set by `Context::into_dispatch`.
+    pub parent: Option<std::rc::Rc<Context<'static>>>,
+    /// Whether an ancestor prompts for missing required flags.
+    pub prompt_missing: bool,
+    /// Terminal replacing the process's stdio.
+    pub terminal: Option<crate::output::Terminal>,
+}
+
+impl Inherited {
+    /// The terminal commands run in, by default the process's stdio.
+    pub fn terminal(&self) -> crate::output::Terminal {
+        self.terminal.clone().unwrap_or_default()
+    }
+}
+
+/// Parsed state of global flags, as found in a parent's `Context`.
+#[doc(hidden)]
+#[derive(Clone, Debug, Default)]
+pub struct GlobalValues {
+    /// Last value of each flag (`None` for boolean flags).
+    pub flags: std::collections::HashMap<String, Option<String>>,
+    /// Every value of flags accepting multiple values.
+    pub values: std::collections::HashMap<String, Vec<String>>,
+    /// Where each value came from.
+    pub sources: std::collections::HashMap<String, crate::provider::ValueSource>,
+}
+
 /// The core trait for a CLI application or sub-command.
 pub trait App {
     /// The name of the application or command.
@@ -47,6 +163,16 @@ pub trait App {
         vec![]
     }
 
+    /// Returns a list of positional arguments for help generation.
+    fn args(&self) -> Vec<crate::arg::ArgDef> {
+        vec![]
+    }
+
+    /// Returns the flag groups constraining how flags combine.
+    fn groups(&self) -> Vec<crate::group::GroupDef> {
+        vec![]
+    }
+
     /// Returns a list of subcommands for help generation.
     fn subcommands(&self) -> Vec<crate::command::CommandDef> {
         vec![]
@@ -65,115 +191,197 @@ pub trait App {
     /// Execute the application logic.
     fn execute(&mut self, ctx: Context) -> KoralResult<()>;
 
+    /// Returns the sources consulted, in order, for flags missing from the command line.
+    ///
+    /// They take precedence over the dotenv file, the configuration file and flag
+    /// defaults, in that fixed order. Defaults to `None`: the parent's providers,
+    /// or the environment only at the root.
+    fn providers(&self) -> Option<Vec<Box<dyn crate::provider::ValueProvider>>> {
+        None
+    }
+
+    /// Name of the dotenv file searched for in the current directory and its parents.
+    ///
+    /// Returning `Some` makes `env` lookups fall back to that file, after the
+    /// real environment. Defaults to `None`.
+    fn dotenv_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Prefix naming the environment variable of every flag without an explicit `env`.
+    ///
+    /// Flags read `<PREFIX>_<FLAG>`, and flags of subcommands read
+    /// `<PREFIX>_<SUBCMD>_<FLAG>`. Defaults to `None`.
+    fn env_prefix(&self) -> Option<&str> {
+        None
+    }
+
+    /// Whether an unambiguous prefix of a long flag selects it (`--verb` for `--verbose`).
+    ///
+    /// Applies to subcommands as well.
+    fn infer_long_args(&self) -> bool {
+        false
+    }
+
+    /// Whether an unambiguous prefix of a subcommand name selects it (`clu` for `clusters`).
+    ///
+    /// Applies to nested subcommands as well.
+    fn infer_subcommands(&self) -> bool {
+        false
+    }
+
+    /// Whether an unrecognised subcommand `foo` runs the executable `<name>-foo` from `PATH`.
+    ///
+    /// Everything after it is passed through unparsed. Ignored by apps with positional
+    /// arguments. Defaults to `false`.
+    fn external_subcommands(&self) -> bool {
+        false
+    }
+
+    /// Whether missing required flags are asked for when run from a terminal.
+    ///
+    /// Booleans get a `y/N` question, flags with possible values a menu and
+    /// sensitive flags hidden input. `--no-input`, or input that is not a
+    /// terminal, keeps the usual missing-flag error. Applies to subcommands as well.
+    fn prompt_missing(&self) -> bool {
+        false
+    }
+
+    /// Whether `@file` arguments are expanded into the arguments listed in that file.
+    fn response_files(&self) -> bool {
+        false
+    }
+
+    /// Name used to locate the configuration file.
+    ///
+    /// Returning `Some` enables the `--config <FILE>` flag and the
+    /// `<NAME>_CONFIG` environment variable. Defaults to `None`.
+    fn config_name(&self) -> Option<&str> {
+        None
+    }
+
     /// Run the application with a shared state.
     fn run_with_state(
         &mut self,
         state: &mut dyn std::any::Any,
         args: Vec<String>,
     ) -> KoralResult<()> {
-        // Check for help flag, but respect subcommands
-        let flags = self.flags();
-        let h_overridden = flags.iter().any(|f| f.short == Some('h'));
-
-        let help_invoked = args.iter().position(|a| {
-            if a == "--help" {
-                true
-            } else if a == "-h" {
-                !h_overridden
-            } else {
-                false
-            }
-        });
-        let subcommands = self.subcommands();
+        self.run_nested(Some(state), args, Inherited::default())
+    }
 
-        let should_print_help = if let Some(h_idx) = help_invoked {
-            // Check if a known subcommand appears BEFORE help
-            // args[0] is prog name, start checking from 1
-            let sub_idx = args.iter().enumerate().skip(1).find_map(|(i, arg)| {
-                if subcommands.iter().any(|s| s.name == *arg) {
-                    Some(i)
-                } else {
-                    None
-                }
-            });
+    /// Run the application with the given arguments.
+    /// This handles common tasks like help and version checks, and argument parsing.
+    fn run(&mut self, args: Vec<String>) -> KoralResult<()> {
+        self.run_nested(None, args, Inherited::default())
+    }
 
-            match sub_idx {
-                Some(s_idx) => h_idx < s_idx, // Print help only if help appears BEFORE subcommand
-                None => true,                 // No subcommand, help invoked -> print help
-            }
-        } else {
-            false
-        };
+    /// Run an interactive session reading commands from the terminal.
+    ///
+    /// See [`Repl`](crate::repl::Repl) to change the prompt, input or history file.
+    fn repl(&mut self) -> KoralResult<()> {
+        crate::repl::Repl::new(self).run()
+    }
 
-        if should_print_help {
-            self.print_help();
-            return Ok(());
-        }
+    /// Run an interactive session whose commands share `state`.
+    fn repl_with_state(&mut self, state: &mut dyn std::any::Any) -> KoralResult<()> {
+        crate::repl::Repl::new(self).run_with_state(state)
+    }
 
-        // Parse arguments
-        let (mut flags_map, mut positionals) = {
-            let parser = crate::parser::Parser::new(self.flags())
-                .strict(self.is_strict())
-                .ignore_required(true);
-            // Skip argv[0] (program name)
-            let args_to_parse = if !args.is_empty() {
-                &args[1..]
-            } else {
-                &args[..]
-            };
-            let ctx = parser.parse(args_to_parse)?;
-            (ctx.flags, ctx.args)
+    /// Run the application as part of a command chain.
+    ///
+    /// Used by `run`, `run_with_state` and subcommand dispatch; `inherited`
+    /// carries what the parent command hands down.
+    #[doc(hidden)]
+    fn run_nested(
+        &mut self,
+        mut state: Option<&mut dyn std::any::Any>,
+        args: Vec<String>,
+        inherited: Inherited,
+    ) -> KoralResult<()> {
+        let terminal = inherited.terminal();
+
+        // Expand response files first, so they may hold `--help` too.
+        // Subcommands receive arguments the root already expanded.
+        let args = if self.response_files() && inherited.path.is_empty() && !args.is_empty() {
+            let mut expanded = vec![args[0].clone()];
+            expanded.extend(crate::response::expand(&args[1..])?);
+            expanded
+        } else {
+            args
         };
 
-        if flags_map.contains_key("version") {
-            println!("{} version {}", self.name(), self.version());
-            return Ok(());
+        // A parent's prefix, already scoped to this command, wins over our own
+        let prefix_from_parent = inherited.env_prefix.is_some();
+        let env_prefix = inherited
+            .env_prefix
+            .or_else(|| self.env_prefix().map(|p| p.to_string()));
+        let root_env_prefix = inherited.root_env_prefix.unwrap_or_else(|| {
+            env_prefix
+                .clone()
+                .unwrap_or_else(|| self.name().to_uppercase().replace('-', "_"))
+        });
+        let mut flags = self.flags();
+        crate::confirm::apply_assume_yes_env(&mut flags, &root_env_prefix);
+        if let Some(prefix) = &env_prefix {
+            crate::flag::apply_env_prefix(&mut flags, prefix);
         }
-
-        let middlewares = self.middlewares();
-        let skip_middleware = help_invoked.is_some();
-
-        let mut extensions = std::collections::HashMap::new();
-
-        // Execute Middleware 'before' hooks
-        // We create a temporary context for BEFORE hooks
-        if !skip_middleware {
-            let mut ctx = Context::new(flags_map.clone(), positionals.clone()).with_state(state);
-            for mw in &middlewares {
-                mw.before(&mut ctx)?;
+        let infer_long_args = inherited.infer_long_args || self.infer_long_args();
+        let infer_subcommands = inherited.infer_subcommands || self.infer_subcommands();
+        let prompt_missing = inherited.prompt_missing || self.prompt_missing();
+
+        // Global flags of the ancestors are accepted here too, unless redefined
+        let has_inherited_globals = !inherited.globals.is_empty();
+        for global in inherited.globals {
+            if !flags.iter().any(|f| f.name == global.name) {
+                flags.push(global);
             }
-            // Capture any modifications to flags/args?
-            // For now, we update our local copies.
-            flags_map = ctx.flags;
-            positionals = ctx.args;
-            extensions = ctx.extensions;
         }
-
-        // Execute Command
-        // Create context for execution (consumes it)
-        let result = {
-            let ctx = Context::new(flags_map.clone(), positionals.clone())
-                .with_state(state)
-                .with_extensions(extensions);
-            self.execute(ctx)
+        let globals: Vec<crate::flag::FlagDef> =
+            flags.iter().filter(|f| f.global).cloned().collect();
+        let subcommands = self.subcommands();
+        // Values of positional arguments are never taken for plugin names
+        let runs_plugins = self.external_subcommands() && self.args().is_empty();
+
+        // The root command locates the configuration file; subcommands reuse it.
+        // A broken file, or broken aliases in it, must not keep `--help` from
+        // working, so errors are only reported once help is ruled out.
+        let is_root = inherited.path.is_empty();
+        let config = match inherited.config {
+            Some(config) => Ok(Some(config)),
+            None => match self.config_name() {
+                Some(name) => {
+                    crate::provider::FileProvider::discover(name, args.get(1..).unwrap_or(&[]))
+                }
+                None => Ok(None),
+            },
         };
 
-        // Execute Middleware 'after' hooks
-        if !skip_middleware && result.is_ok() {
-            let mut ctx = Context::new(flags_map, positionals).with_state(state);
-            for mw in middlewares.iter().rev() {
-                mw.after(&mut ctx)?;
+        // Expand user aliases of the root's commands before anything looks at them
+        let aliases = match &config {
+            Ok(Some(config)) if is_root => crate::alias::load(config, &subcommands),
+            _ => Ok(vec![]),
+        };
+        let expanded = match &aliases {
+            Ok(aliases) if !aliases.is_empty() && !args.is_empty() => {
+                crate::alias::expand(args[1..].to_vec(), aliases, &flags).map(|rest| {
+                    let mut expanded = vec![args[0].clone()];
+                    expanded.extend(rest);
+                    Some(expanded)
+                })
             }
-        }
-
-        result
-    }
+            _ => Ok(None),
+        };
+        let (args, expand_error) = match expanded {
+            Ok(Some(expanded)) => (expanded, None),
+            Ok(None) => (args, None),
+            Err(e) => (args, Some(e)),
+        };
+        let (aliases, alias_error) = match aliases {
+            Ok(aliases) => (aliases, expand_error),
+            Err(e) => (vec![], Some(e)),
+        };
 
-    /// Run the application with the given arguments.
-    /// This handles common tasks like help and version checks, and argument parsing.
-    fn run(&mut self, args: Vec<String>) -> KoralResult<()> {
         // Check for help flag, but respect subcommands
-        let flags = self.flags();
         let h_overridden = flags.iter().any(|f| f.short == Some('h'));
 
         let help_invoked = args.iter().position(|a| {
@@ -185,19 +393,19 @@ pub trait App {
                 false
             }
         });
-        let subcommands = self.subcommands();
 
         let should_print_help = if let Some(h_idx) = help_invoked {
             // Check if a known subcommand appears BEFORE help
             // args[0] is prog name, start checking from 1
+            let known = if runs_plugins {
+                crate::external::with_plugins(self)
+            } else {
+                subcommands.clone()
+            };
             let sub_idx = args.iter().enumerate().skip(1).find_map(|(i, arg)| {
-                if subcommands
-                    .iter()
-                    .any(|s| s.name == *arg || s.aliases.contains(arg))
-                {
-                    Some(i)
-                } else {
-                    None
+                match crate::command::resolve_subcommand(&known, arg, infer_subcommands) {
+                    Ok(Some(_)) => Some(i),
+                    _ => None,
                 }
             });
 
@@ -210,55 +418,164 @@ pub trait App {
         };
 
         if should_print_help {
-            self.print_help();
+            if prefix_from_parent || has_inherited_globals || !aliases.is_empty() {
+                // Subcommands cannot see the parent's prefix or global flags on their own
+                terminal.print(&crate::help::generate_help_with_flags(
+                    self,
+                    flags,
+                    &aliases,
+                    terminal.width(),
+                ))?;
+            } else {
+                self.write_help(&terminal)?;
+            }
             return Ok(());
         }
+        let config = config?;
+        if let Some(e) = alias_error {
+            return Err(e);
+        }
+
+        // Skip argv[0] (program name)
+        let args_to_parse = if args.is_empty() {
+            &args[..]
+        } else {
+            &args[1..]
+        };
+
+        let dotenv = match inherited.dotenv {
+            Some(dotenv) => Some(dotenv),
+            None => match self.dotenv_name() {
+                Some(name) => crate::provider::DotenvProvider::discover(name)?,
+                None => None,
+            },
+        };
+        // A command without providers of its own uses its parent's
+        let provider_chain = match self.providers() {
+            Some(own) => crate::provider::ProviderChain::new(own),
+            None => inherited.providers.unwrap_or_else(|| {
+                crate::provider::ProviderChain::new(crate::provider::default_providers())
+            }),
+        };
+        let mut providers = provider_chain.providers();
+        if let Some(dotenv) = &dotenv {
+            providers.push(Box::new(dotenv.clone()));
+        }
+        let scoped = config.clone().map(|c| {
+            if inherited.path.is_empty() {
+                c
+            } else {
+                c.section(inherited.path.join("."))
+            }
+        });
 
         // Parse arguments
-        let (mut flags_map, mut positionals) = {
-            let parser = crate::parser::Parser::new(self.flags())
+        let required: Vec<crate::flag::FlagDef> =
+            flags.iter().filter(|f| f.required).cloned().collect();
+        let mut parsed = {
+            let parser = crate::parser::Parser::new(flags)
+                .args(self.args())
+                .groups(self.groups())
+                .with_providers(providers)
+                .config(scoped)
                 .strict(self.is_strict())
+                .infer_long_args(infer_long_args)
+                .external_subcommands(runs_plugins.then(|| subcommands.clone()))
                 .ignore_required(true);
-            // Skip argv[0] (program name)
-            let args_to_parse = if args.is_empty() {
-                &args[..]
-            } else {
-                &args[1..]
-            };
-            let ctx = parser.parse(args_to_parse)?;
-            (ctx.flags, ctx.args)
+            parser.parse(args_to_parse)?
         };
 
-        if flags_map.contains_key("version") {
-            println!("{} version {}", self.name(), self.version());
+        // Spell out an abbreviated subcommand for dispatch
+        if infer_subcommands {
+            if let Some(first) = parsed.args.first() {
+                let mut name = crate::command::resolve_subcommand(&subcommands, first, true)?
+                    .map(|sub| sub.name.clone());
+                // Plugins are only searched for names no built-in command takes
+                if name.is_none() && runs_plugins {
+                    let plugins = crate::external::with_plugins(self);
+                    name = crate::command::resolve_subcommand(&plugins, first, true)?
+                        .map(|sub| sub.name.clone());
+                }
+                if let Some(name) = name {
+                    parsed.args[0] = name;
+                }
+            }
+        }
+
+        // Values given to an ancestor apply unless given again here
+        parsed.merge_globals(inherited.global_values);
+
+        if parsed.flags.contains_key("version") {
+            writeln!(
+                terminal.stdout(),
+                "{} version {}",
+                self.name(),
+                self.version()
+            )?;
             return Ok(());
         }
 
+        // Ask for required flags, unless a subcommand or plugin takes over.
+        // Without declared arguments, a plugin takes any name no subcommand does.
+        let dispatches = parsed.args.first().is_some_and(|first| {
+            runs_plugins
+                || crate::command::resolve_subcommand(&subcommands, first, infer_subcommands)
+                    .is_ok_and(|sub| sub.is_some())
+        });
+        if prompt_missing && !dispatches && !parsed.is_present(crate::prompt::NO_INPUT_FLAG) {
+            crate::prompt::fill_missing(&required, &mut parsed, &terminal.input())?;
+        }
+
         let middlewares = self.middlewares();
         let skip_middleware = help_invoked.is_some();
-
         let mut extensions = std::collections::HashMap::new();
+        parsed = parsed.with_parent(inherited.parent);
 
+        // Execute Middleware 'before' hooks
+        // We create a temporary context for BEFORE hooks
         if !skip_middleware {
-            let mut ctx = Context::new(flags_map.clone(), positionals.clone());
+            let mut ctx = parsed.snapshot().with_state_opt(state.as_deref_mut());
+            ctx.inherited.terminal = Some(terminal.clone());
             for mw in &middlewares {
                 mw.before(&mut ctx)?;
             }
-            flags_map = ctx.flags;
-            positionals = ctx.args;
+            // Capture any modifications to flags/args?
+            // For now, we update our local copies.
+            parsed = ctx.snapshot();
             extensions = ctx.extensions;
         }
 
+        let inherited = Inherited {
+            path: inherited.path,
+            config,
+            dotenv,
+            providers: Some(provider_chain),
+            env_prefix,
+            root_env_prefix: Some(root_env_prefix),
+            infer_long_args,
+            infer_subcommands,
+            global_values: parsed.global_values(&globals),
+            globals,
+            parent: None,
+            prompt_missing,
+            terminal: Some(terminal.clone()),
+        };
+
         // Execute Command
+        // Create context for execution (consumes it)
         let result = {
-            let ctx =
-                Context::new(flags_map.clone(), positionals.clone()).with_extensions(extensions);
+            let ctx = parsed
+                .snapshot()
+                .with_state_opt(state.as_deref_mut())
+                .with_extensions(extensions)
+                .with_inherited(inherited);
             self.execute(ctx)
         };
 
         // Execute Middleware 'after' hooks
         if !skip_middleware && result.is_ok() {
-            let mut ctx = Context::new(flags_map, positionals);
+            let mut ctx = parsed.snapshot().with_state_opt(state);
+            ctx.inherited.terminal = Some(terminal);
             for mw in middlewares.iter().rev() {
                 mw.after(&mut ctx)?;
             }
@@ -267,8 +584,22 @@ pub trait App {
         result
     }
 
-    /// Print help message to stdout.
+    /// Write the help message to the terminal's stdout.
+    fn write_help(&self, terminal: &crate::output::Terminal) -> KoralResult<()> {
+        let mut flags = self.flags();
+        if let Some(prefix) = self.env_prefix() {
+            crate::flag::apply_env_prefix(&mut flags, prefix);
+        }
+        terminal.print(&crate::help::generate_help_with_flags(
+            self,
+            flags,
+            &[],
+            terminal.width(),
+        ))
+    }
+
+    /// Print the help message to stdout.
     fn print_help(&self) {
-        print!("{}", crate::help::generate_help(self));
+        let _ = self.write_help(&crate::output::Terminal::stdio());
     }
 }
@@ -14,7 +14,7 @@ enum Commands {
 
 impl Default for Commands {
     fn default() -> Self {
-        Self::Add(AddCmd::default())
+        Self::Add(AddCmd)
     }
 }
 
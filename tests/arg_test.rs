@@ -0,0 +1,157 @@
+mod common;
+
+use common::parse;
+use koral::completion::{generate_to, Shell};
+use koral::help::generate_help;
+use koral::prelude::*;
+use koral::testing;
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(name = "src", value_name = "FILE", help = "Files to copy")]
+struct Src(Vec<String>);
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(name = "dst", value_name = "DIR", help = "Destination directory")]
+struct Dst(String);
+
+#[derive(Default, App)]
+#[app(name = "cp", action = copy)]
+#[app(args(Src, Dst))]
+struct CopyApp;
+
+fn copy(src: ArgVal<Src>, dst: ArgVal<Dst>) -> KoralResult<()> {
+    assert_eq!(*src, vec!["a.txt".to_string(), "b.txt".to_string()]);
+    assert_eq!(*dst, "out");
+    Ok(())
+}
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(help = "Number of lines")]
+struct CountArg(u32);
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(arity = "optional")]
+struct Label(String);
+
+#[derive(Default, App)]
+#[app(name = "head")]
+#[app(args(CountArg, Label))]
+struct HeadApp;
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(name = "name")]
+struct NameArg(String);
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(name = "greeting", arity = "optional")]
+struct GreetingArg(String);
+
+fn greet(
+    term: Terminal,
+    name: ArgVal<NameArg>,
+    greeting: Option<ArgVal<GreetingArg>>,
+) -> KoralResult<()> {
+    let greeting = greeting.map_or("hello".to_string(), |g| g.0);
+    term.print(&format!("{} {}\n", greeting, *name))
+}
+
+#[derive(Default, App)]
+#[app(name = "greet", action = greet)]
+#[app(args(NameArg, GreetingArg))]
+struct GreetApp;
+
+#[test]
+fn test_args_bind_in_order() {
+    let ctx = parse(&CopyApp, &["a.txt", "b.txt", "out"]).unwrap();
+    assert_eq!(
+        ctx.arg::<Src>(),
+        Some(vec!["a.txt".to_string(), "b.txt".to_string()])
+    );
+    assert_eq!(ctx.arg::<Dst>(), Some("out".to_string()));
+}
+
+#[test]
+fn test_arg_val_extractor() {
+    let mut app = CopyApp;
+    let args = ["cp", "a.txt", "b.txt", "out"];
+    app.run(args.iter().map(|s| s.to_string()).collect())
+        .unwrap();
+}
+
+#[test]
+fn test_missing_required_arg() {
+    let err = parse(&CopyApp, &[]).unwrap_err();
+    match err {
+        KoralError::MissingArgument(msg) => {
+            assert!(msg.contains("Required argument '<DIR>' is missing"));
+        }
+        _ => panic!("Expected MissingArgument error, got {:?}", err),
+    }
+}
+
+#[test]
+fn test_optional_arg_and_typed_value() {
+    let ctx = parse(&HeadApp, &["10"]).unwrap();
+    assert_eq!(ctx.arg::<CountArg>(), Some(10));
+    assert_eq!(ctx.arg::<Label>(), None);
+
+    let ctx = parse(&HeadApp, &["10", "top"]).unwrap();
+    assert_eq!(ctx.arg::<Label>(), Some("top".to_string()));
+}
+
+#[test]
+fn test_arg_parse_error() {
+    let res = parse(&HeadApp, &["ten"]);
+    assert!(matches!(res, Err(KoralError::FlagValueParseError(_))));
+}
+
+#[test]
+fn test_surplus_args_are_rejected() {
+    let err = parse(&HeadApp, &["10", "top", "extra"]).unwrap_err();
+    match err {
+        KoralError::InvalidArgument(msg) => assert_eq!(msg, "Unexpected argument 'extra'"),
+        _ => panic!("Expected InvalidArgument error, got {:?}", err),
+    }
+
+    testing::run(&mut GreetApp, ["greet", "bob", "hey", "extra"])
+        .assert_exit_code(2)
+        .assert_stderr_contains("Unexpected argument 'extra'");
+}
+
+#[test]
+fn test_optional_arg_extractor() {
+    testing::run(&mut GreetApp, ["greet", "bob"])
+        .assert_success()
+        .assert_stdout_contains("hello bob\n");
+    testing::run(&mut GreetApp, ["greet", "bob", "Hi"])
+        .assert_success()
+        .assert_stdout_contains("Hi bob\n");
+}
+
+#[test]
+fn test_help_shows_arguments() {
+    let help = generate_help(&HeadApp);
+    assert!(help.contains("[options] <COUNT> [LABEL]"));
+    assert!(help.contains("Arguments:"));
+    assert!(help.contains("Number of lines"));
+}
+
+#[test]
+fn test_man_page_shows_arguments() {
+    let man = koral::man::generate_man_page(&CopyApp, "2024-01-01");
+    assert!(man.contains(".SH ARGUMENTS"));
+    assert!(man.contains("[FILE]..."));
+}
+
+#[test]
+fn test_completion_hints_paths() {
+    let mut buf = Vec::new();
+    generate_to(&CopyApp, Shell::Zsh, &mut buf).unwrap();
+    let zsh = String::from_utf8(buf).unwrap();
+    assert!(zsh.contains("'*:FILE:_files'"));
+
+    let mut buf = Vec::new();
+    generate_to(&CopyApp, Shell::Bash, &mut buf).unwrap();
+    let bash = String::from_utf8(buf).unwrap();
+    assert!(bash.contains("compgen -f"));
+}
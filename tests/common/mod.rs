@@ -0,0 +1,22 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use koral::internal::parser::Parser;
+use koral::traits::App;
+use koral::{Context, KoralResult};
+
+/// Parser for the flags and positional arguments of `app`.
+pub fn parser(app: &impl App) -> Parser {
+    Parser::new(app.flags()).args(app.args())
+}
+
+/// Parse `args`, given without the program name, as `app` would.
+pub fn parse(app: &impl App, args: &[&str]) -> KoralResult<Context<'static>> {
+    parse_with(&parser(app), args)
+}
+
+/// Parse `args`, given without the program name, with `parser`.
+pub fn parse_with(parser: &Parser, args: &[&str]) -> KoralResult<Context<'static>> {
+    let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+    parser.parse(&args)
+}
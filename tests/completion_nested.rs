@@ -81,5 +81,5 @@ fn test_nested_completion_generated() {
 
     assert!(output_fish.contains("complete -c root -a 'sub'"));
     assert!(output_fish.contains("complete -c root -a 'deep'"));
-    assert!(output_fish.contains("--subflag"));
+    assert!(output_fish.contains("-l subflag"));
 }
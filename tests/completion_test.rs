@@ -56,7 +56,6 @@ fn test_fish_completion() {
     generate_to(&app, Shell::Fish, &mut buf).unwrap();
 
     let output = String::from_utf8(buf).unwrap();
-    std::fs::write("completion_debug.txt", &output).unwrap();
 
     assert!(output.contains("complete -c myprog"));
 
@@ -0,0 +1,90 @@
+mod common;
+
+use koral::prelude::*;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "mode", default = "local")]
+struct ModeFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "host", required_if_eq("mode", "remote"))]
+struct HostFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "config")]
+struct ConfigFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "token", required_unless_present("config"))]
+struct TokenFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "retries", default = "1", default_value_if("env", "prod", "3"))]
+struct RetriesFlag(u32);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "env", default = "dev")]
+struct EnvFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "deploy", action = deploy)]
+#[app(flags(ModeFlag, HostFlag, ConfigFlag, TokenFlag, RetriesFlag, EnvFlag))]
+struct DeployApp;
+
+fn deploy(_ctx: Context) -> KoralResult<()> {
+    Ok(())
+}
+
+fn parse(args: &[&str]) -> KoralResult<Context<'static>> {
+    common::parse(&DeployApp, args)
+}
+
+fn run(args: &[&str]) -> KoralResult<()> {
+    let mut app = DeployApp;
+    let mut argv = vec!["deploy".to_string()];
+    argv.extend(args.iter().map(|s| s.to_string()));
+    app.run(argv)
+}
+
+#[test]
+fn test_required_if_eq_names_trigger() {
+    assert!(parse(&["--token", "t"]).is_ok());
+
+    let err = parse(&["--token", "t", "--mode", "remote"]).unwrap_err();
+    match err {
+        KoralError::MissingArgument(msg) => {
+            assert!(msg.contains("'--host' is required when '--mode' is 'remote'"))
+        }
+        _ => panic!("Expected MissingArgument error, got {:?}", err),
+    }
+
+    assert!(parse(&["--token", "t", "--mode", "remote", "--host", "h"]).is_ok());
+}
+
+#[test]
+fn test_required_unless_present() {
+    let err = parse(&[]).unwrap_err();
+    assert!(matches!(
+        err,
+        KoralError::MissingArgument(ref m) if m.contains("'--token' is required unless '--config' is present")
+    ));
+    assert!(parse(&["--config", "c.toml"]).is_ok());
+}
+
+#[test]
+fn test_conditions_enforced_at_execution() {
+    assert!(run(&["--mode", "remote", "--config", "c"]).is_err());
+    assert!(run(&["--mode", "remote", "--config", "c", "--host", "h"]).is_ok());
+}
+
+#[test]
+fn test_default_value_if() {
+    let ctx = parse(&["--config", "c"]).unwrap();
+    assert_eq!(ctx.get::<RetriesFlag>(), Some(1));
+
+    let ctx = parse(&["--config", "c", "--env", "prod"]).unwrap();
+    assert_eq!(ctx.get::<RetriesFlag>(), Some(3));
+
+    let ctx = parse(&["--config", "c", "--env", "prod", "--retries", "5"]).unwrap();
+    assert_eq!(ctx.get::<RetriesFlag>(), Some(5));
+}
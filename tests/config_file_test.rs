@@ -0,0 +1,186 @@
+use koral::config::ConfigFormat;
+use koral::prelude::*;
+use koral::provider::{FileProvider, ValueProvider};
+use koral::testing::Harness;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+type Seen = Arc<Mutex<Vec<String>>>;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", default = "local")]
+struct RegionFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "replicas", env = "CFGTEST_REPLICAS")]
+struct ReplicasFlag(u32);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "dry-run")]
+struct DryRunFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "tag")]
+struct TagFlag(Vec<String>);
+
+fn record(ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "region={} replicas={} dry_run={} tags={}",
+        ctx.get::<RegionFlag>().unwrap_or_default(),
+        ctx.get::<ReplicasFlag>().unwrap_or_default(),
+        ctx.get::<DryRunFlag>().unwrap_or_default(),
+        ctx.get::<TagFlag>().unwrap_or_default().join(",")
+    );
+    ctx.state::<Seen>().unwrap().lock().unwrap().push(line);
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "cfgtest", action = record, config_file)]
+#[app(flags(RegionFlag, ReplicasFlag, DryRunFlag, TagFlag))]
+struct CfgApp;
+
+#[derive(Default, App)]
+#[app(name = "deploy", action = record)]
+#[app(flags(RegionFlag, ReplicasFlag))]
+struct DeployCmd;
+
+#[derive(Subcommand)]
+enum Commands {
+    Deploy(DeployCmd),
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::Deploy(DeployCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "cfgnested", config_file = "cfgnested-tool")]
+#[app(flags(RegionFlag))]
+struct NestedApp {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+fn write_config(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("koral-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn run(app: &mut impl koral::traits::App, args: &[&str]) -> Vec<String> {
+    run_in(&Harness::new(), app, args)
+}
+
+fn run_in(harness: &Harness, app: &mut impl koral::traits::App, args: &[&str]) -> Vec<String> {
+    let mut seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    harness
+        .run_with_state(app, &mut seen, args.to_vec())
+        .assert_success();
+    let out = seen.lock().unwrap().clone();
+    out
+}
+
+#[test]
+fn test_toml_config_values() {
+    let path = write_config(
+        "basic.toml",
+        "region = \"eu-west-1\"\nreplicas = 3\ndry_run = true\ntag = [\"a\", \"b\"]\n",
+    );
+    let out = run(
+        &mut CfgApp,
+        &["cfgtest", "--config", path.to_str().unwrap()],
+    );
+    assert_eq!(
+        out,
+        vec!["region=eu-west-1 replicas=3 dry_run=true tags=a,b"]
+    );
+}
+
+#[test]
+fn test_json_and_ini_config_values() {
+    let json = write_config("basic.json", r#"{"region": "ap-1", "replicas": 2}"#);
+    let out = run(
+        &mut CfgApp,
+        &["cfgtest", &format!("--config={}", json.display())],
+    );
+    assert_eq!(out, vec!["region=ap-1 replicas=2 dry_run=false tags="]);
+
+    let ini = write_config("basic.ini", "; comment\nregion = us-2\ndry-run = true\n");
+    let out = run(&mut CfgApp, &["cfgtest", "--config", ini.to_str().unwrap()]);
+    assert_eq!(out, vec!["region=us-2 replicas=0 dry_run=true tags="]);
+}
+
+#[test]
+fn test_precedence_cli_env_file_default() {
+    let path = write_config("precedence.toml", "replicas = 3\n");
+    let config = path.to_str().unwrap();
+
+    let out = run(&mut CfgApp, &["cfgtest", "--config", config]);
+    assert_eq!(out, vec!["region=local replicas=3 dry_run=false tags="]);
+
+    let harness = Harness::new().env("CFGTEST_REPLICAS", "5");
+    let from_env = run_in(&harness, &mut CfgApp, &["cfgtest", "--config", config]);
+    let from_cli = run_in(
+        &harness,
+        &mut CfgApp,
+        &["cfgtest", "--config", config, "--replicas", "7"],
+    );
+
+    assert_eq!(
+        from_env,
+        vec!["region=local replicas=5 dry_run=false tags="]
+    );
+    assert_eq!(
+        from_cli,
+        vec!["region=local replicas=7 dry_run=false tags="]
+    );
+}
+
+#[test]
+fn test_config_path_from_env_var() {
+    let path = write_config(
+        "nested.toml",
+        "region = \"top\"\n\n[deploy]\nreplicas = 4\n",
+    );
+    let harness = Harness::new().env("CFGNESTED_TOOL_CONFIG", path.display().to_string());
+    let out = run_in(
+        &harness,
+        &mut NestedApp::default(),
+        &["cfgnested", "deploy"],
+    );
+
+    // The `[deploy]` section applies to the subcommand, top-level keys still fill in
+    assert_eq!(out, vec!["region=top replicas=4 dry_run=false tags="]);
+}
+
+#[test]
+fn test_missing_config_file_is_an_error() {
+    let mut seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    let args = ["cfgtest", "--config", "/nonexistent/koral/config.toml"];
+    let err = CfgApp
+        .run_with_state(&mut seen, args.iter().map(|s| s.to_string()).collect())
+        .unwrap_err();
+    assert!(matches!(err, KoralError::IoError(_)));
+}
+
+#[test]
+fn test_file_provider_sections() {
+    let provider = FileProvider::parse("[k8s.clusters]\nregion = \"inner\"\n", ConfigFormat::Ini)
+        .unwrap()
+        .section("k8s.clusters");
+    let flag = koral::internal::flag::FlagDef::from_trait::<RegionFlag>();
+    assert_eq!(provider.get_value(&flag), Some("inner".to_string()));
+}
+
+#[test]
+fn test_help_lists_config_flag() {
+    let help = koral::help::generate_help(&CfgApp);
+    assert!(help.contains("--config"));
+    assert!(help.contains("<FILE>"));
+    assert!(help.contains("Path to the configuration file"));
+}
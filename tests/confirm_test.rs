@@ -0,0 +1,144 @@
+use koral::prelude::*;
+use koral::prompt::{run_with_io, PromptIo};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+type Seen = Arc<Mutex<Vec<String>>>;
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(name = "name")]
+struct ClusterName(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", default = "us-east-1")]
+struct RegionFlag(String);
+
+fn delete(name: ArgVal<ClusterName>, seen: State<Seen>) -> KoralResult<()> {
+    seen.lock().unwrap().push(format!("deleted {}", *name));
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "delete", action = delete, confirm = "Delete cluster {name} in {region}?")]
+#[app(args(ClusterName), flags(RegionFlag))]
+struct DeleteCmd;
+
+fn prune(args: Args, confirm: Confirm, seen: State<Seen>) -> KoralResult<()> {
+    for name in args.iter() {
+        if confirm.ask(&format!("Prune {}?", name))? {
+            seen.lock().unwrap().push(format!("pruned {}", name));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "prune", action = prune, confirm)]
+struct PruneCmd;
+
+#[derive(Subcommand)]
+enum Commands {
+    Delete(DeleteCmd),
+    Prune(PruneCmd),
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::Prune(PruneCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "ops", env_prefix = "CONFIRM_TEST")]
+struct OpsApp {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+/// Answers prompts from a script, recording what was shown.
+struct Script {
+    interactive: bool,
+    answers: VecDeque<String>,
+    transcript: Rc<RefCell<String>>,
+}
+
+impl PromptIo for Script {
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    fn write(&mut self, text: &str) -> KoralResult<()> {
+        self.transcript.borrow_mut().push_str(text);
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> KoralResult<Option<String>> {
+        Ok(self.answers.pop_front())
+    }
+}
+
+fn run(interactive: bool, answers: &[&str], args: &[&str]) -> (KoralResult<Vec<String>>, String) {
+    let transcript = Rc::new(RefCell::new(String::new()));
+    let io = Script {
+        interactive,
+        answers: answers.iter().map(|s| s.to_string()).collect(),
+        transcript: transcript.clone(),
+    };
+    let mut seen = Seen::default();
+    let mut full = vec!["ops".to_string()];
+    full.extend(args.iter().map(|s| s.to_string()));
+    let result = run_with_io(&mut OpsApp::default(), io, Some(&mut seen), full)
+        .map(|_| seen.lock().unwrap().clone());
+    let transcript = transcript.borrow().clone();
+    (result, transcript)
+}
+
+#[test]
+fn test_confirmed_command_runs() {
+    let (result, transcript) = run(true, &["yes"], &["delete", "prod", "--region", "eu"]);
+    assert_eq!(result.unwrap(), vec!["deleted prod"]);
+    assert_eq!(transcript, "Delete cluster prod in eu? [y/N] ");
+}
+
+#[test]
+fn test_declined_command_is_aborted() {
+    let (result, _) = run(true, &["n"], &["delete", "prod"]);
+    assert!(matches!(result, Err(KoralError::Aborted(_))));
+    let (result, _) = run(true, &[], &["delete", "prod"]);
+    assert!(matches!(result, Err(KoralError::Aborted(_))));
+}
+
+#[test]
+fn test_yes_flag_skips_question() {
+    for yes in ["--yes", "-y", "--assume-yes"] {
+        let (result, transcript) = run(false, &[], &["delete", "prod", yes]);
+        assert_eq!(result.unwrap(), vec!["deleted prod"]);
+        assert!(transcript.is_empty());
+    }
+}
+
+#[test]
+fn test_non_interactive_refuses_without_yes() {
+    let (result, transcript) = run(false, &["y"], &["delete", "prod"]);
+    assert!(matches!(result, Err(KoralError::Aborted(ref msg)) if msg.contains("--yes")));
+    assert!(transcript.is_empty());
+}
+
+#[test]
+fn test_assume_yes_env_var() {
+    let mut seen = Seen::default();
+    koral::testing::Harness::new()
+        .env("CONFIRM_TEST_ASSUME_YES", "1")
+        .run_with_state(&mut OpsApp::default(), &mut seen, ["ops", "prune", "a"])
+        .assert_success();
+    assert_eq!(*seen.lock().unwrap(), vec!["pruned a"]);
+}
+
+#[test]
+fn test_confirm_extractor_asks_per_item() {
+    let (result, transcript) = run(true, &["y", "n"], &["prune", "a", "b"]);
+    assert_eq!(result.unwrap(), vec!["pruned a"]);
+    assert_eq!(transcript, "Prune a? [y/N] Prune b? [y/N] ");
+}
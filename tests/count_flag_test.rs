@@ -0,0 +1,75 @@
+mod common;
+
+use koral::prelude::*;
+use koral::testing::Harness;
+
+#[derive(Flag, Debug, Clone, PartialEq)]
+#[flag(name = "verbose", short = 'v', count)]
+struct Verbose(u8);
+
+#[derive(Flag, Debug, Clone, PartialEq)]
+#[flag(name = "debug", count, env = "KORAL_TEST_DEBUG_LEVEL")]
+struct DebugLevel(u8);
+
+#[derive(Flag, Debug, Clone, PartialEq)]
+#[flag(name = "quiet", short = 'q')]
+struct QuietFlag;
+
+#[derive(Default, App)]
+#[app(name = "count_test", action = report)]
+#[app(flags(Verbose, QuietFlag, DebugLevel))]
+struct CountApp;
+
+fn report(ctx: Context) -> KoralResult<()> {
+    ctx.terminal()
+        .print(&format!("debug={:?}\n", ctx.get::<DebugLevel>()))
+}
+
+fn parse(args: &[&str]) -> KoralResult<Context<'static>> {
+    common::parse(&CountApp, args)
+}
+
+#[test]
+fn test_count_separate_short_flags() {
+    let ctx = parse(&["-v", "-v"]).unwrap();
+    assert_eq!(ctx.get::<Verbose>(), Some(2));
+}
+
+#[test]
+fn test_count_combined_short_flags() {
+    let ctx = parse(&["-vvv"]).unwrap();
+    assert_eq!(ctx.get::<Verbose>(), Some(3));
+
+    let ctx = parse(&["-vqv"]).unwrap();
+    assert_eq!(ctx.get::<Verbose>(), Some(2));
+    assert_eq!(ctx.get::<QuietFlag>(), Some(true));
+}
+
+#[test]
+fn test_count_long_and_mixed_flags() {
+    let ctx = parse(&["--verbose", "--verbose", "-v"]).unwrap();
+    assert_eq!(ctx.get::<Verbose>(), Some(3));
+}
+
+#[test]
+fn test_count_defaults_to_zero() {
+    let ctx = parse(&[]).unwrap();
+    assert!(!ctx.is_present("verbose"));
+    assert_eq!(ctx.get::<Verbose>(), Some(0));
+}
+
+#[test]
+fn test_count_does_not_consume_next_argument() {
+    let ctx = parse(&["-v", "file.txt"]).unwrap();
+    assert_eq!(ctx.get::<Verbose>(), Some(1));
+    assert_eq!(ctx.args, vec!["file.txt".to_string()]);
+}
+
+#[test]
+fn test_count_from_env() {
+    Harness::new()
+        .env("KORAL_TEST_DEBUG_LEVEL", "2")
+        .run(&mut CountApp, ["count_test"])
+        .assert_success()
+        .assert_stdout_contains("debug=Some(2)");
+}
@@ -25,9 +25,9 @@ impl FromStr for Speed {
     }
 }
 
-impl ToString for Speed {
-    fn to_string(&self) -> String {
-        self.0.to_string()
+impl std::fmt::Display for Speed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
@@ -90,8 +90,11 @@ fn test_enum_parsing() {
 
 #[test]
 fn test_enum_error() {
-    let res = run_test(vec!["prog", "--color", "Yellow"]).unwrap();
-    assert_eq!(res.color, None, "Invalid enum should result in None");
+    let err = run_test(vec!["prog", "--color", "Yellow"]).unwrap_err();
+    assert!(
+        matches!(err, KoralError::Validation(ref m) if m.contains("possible values: red, green, blue")),
+        "Invalid enum should be rejected by the parser"
+    );
 }
 
 #[test]
@@ -17,7 +17,7 @@ enum ChildCmd {
 
 impl Default for ChildCmd {
     fn default() -> Self {
-        Self::Add(AddCmd::default())
+        Self::Add(AddCmd)
     }
 }
 
@@ -0,0 +1,112 @@
+use koral::prelude::*;
+use koral::testing::Harness;
+use std::path::PathBuf;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "token", env = "DOTENV_TEST_TOKEN")]
+struct TokenFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "user", env = "DOTENV_TEST_USER", default = "nobody")]
+struct UserFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "dotenv_app", action = capture, dotenv)]
+#[app(flags(TokenFlag, UserFlag))]
+struct DotenvApp;
+
+#[derive(Default, App)]
+#[app(name = "plain_app", action = capture)]
+#[app(flags(TokenFlag, UserFlag))]
+struct PlainApp;
+
+fn capture(mut ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "{} {} {}",
+        ctx.get::<TokenFlag>().unwrap_or_default(),
+        ctx.get::<UserFlag>().unwrap_or_default(),
+        ctx.source_of("token")
+            .map(|s| s.to_string())
+            .unwrap_or_default()
+    );
+    ctx.state_mut::<Vec<String>>().unwrap().push(line);
+    Ok(())
+}
+
+fn run(
+    harness: &Harness,
+    app: &mut impl koral::traits::App,
+    args: &[&str],
+) -> KoralResult<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    harness
+        .run_with_state(app, &mut out, args.to_vec())
+        .result?;
+    Ok(out)
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("koral-dotenv-{}", std::process::id()))
+        .join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_dotenv_from_koral_dotenv() {
+    let path = temp_dir("explicit").join("custom.env");
+    std::fs::write(
+        &path,
+        "# secrets\nexport DOTENV_TEST_TOKEN=\"from file\"\nDOTENV_TEST_USER='alice' # me\n",
+    )
+    .unwrap();
+
+    let harness = Harness::new().env("KORAL_DOTENV", path.display().to_string());
+    let out = run(&harness, &mut DotenvApp, &["dotenv_app"]);
+    let plain = run(&harness, &mut PlainApp, &["plain_app"]);
+
+    assert_eq!(
+        out.unwrap(),
+        vec!["from file alice dotenv DOTENV_TEST_TOKEN"]
+    );
+    // Apps without the attribute ignore the file
+    assert_eq!(plain.unwrap(), vec![" nobody "]);
+}
+
+#[test]
+fn test_real_env_wins_over_dotenv() {
+    let path = temp_dir("precedence").join(".env");
+    std::fs::write(&path, "DOTENV_TEST_TOKEN=file\n").unwrap();
+
+    let harness = Harness::new()
+        .env("KORAL_DOTENV", path.display().to_string())
+        .env("DOTENV_TEST_TOKEN", "real");
+    let from_env = run(&harness, &mut DotenvApp, &["dotenv_app"]);
+    let from_cli = run(&harness, &mut DotenvApp, &["dotenv_app", "--token", "cli"]);
+
+    assert_eq!(from_env.unwrap(), vec!["real nobody env DOTENV_TEST_TOKEN"]);
+    assert_eq!(from_cli.unwrap(), vec!["cli nobody command line"]);
+}
+
+#[test]
+fn test_dotenv_found_in_parent_directory() {
+    let root = temp_dir("search");
+    std::fs::write(root.join(".env"), "DOTENV_TEST_TOKEN=parent\n").unwrap();
+    let nested = root.join("a").join("b");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let out = run(
+        &Harness::new().cwd(&nested),
+        &mut DotenvApp,
+        &["dotenv_app"],
+    );
+    assert_eq!(out.unwrap(), vec!["parent nobody dotenv DOTENV_TEST_TOKEN"]);
+}
+
+#[test]
+fn test_missing_koral_dotenv_file_is_an_error() {
+    let harness = Harness::new().env("KORAL_DOTENV", "/nonexistent/koral/.env");
+    let res = run(&harness, &mut DotenvApp, &["dotenv_app"]);
+    assert!(matches!(res, Err(KoralError::IoError(_))));
+}
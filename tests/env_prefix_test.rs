@@ -0,0 +1,102 @@
+use koral::help::generate_help;
+use koral::man::generate_man_page;
+use koral::prelude::*;
+use koral::testing::Harness;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "token")]
+struct TokenFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "log-level", env = "LOG_LEVEL", default = "info")]
+struct LogLevelFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", default = "local")]
+struct RegionFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "deploy", action = deploy)]
+#[app(flags(RegionFlag))]
+struct DeployCmd;
+
+fn deploy(mut ctx: Context) -> KoralResult<()> {
+    let region = ctx.get::<RegionFlag>().unwrap_or_default();
+    ctx.state_mut::<Vec<String>>()
+        .unwrap()
+        .push(format!("deploy {}", region));
+    Ok(())
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Deploy(DeployCmd),
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::Deploy(DeployCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "cloud", action = root, env_prefix = "CLOUD_CLI")]
+#[app(flags(TokenFlag, LogLevelFlag))]
+struct CloudApp {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+fn root(mut ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "root {} {}",
+        ctx.get::<TokenFlag>().unwrap_or_default(),
+        ctx.get::<LogLevelFlag>().unwrap_or_default()
+    );
+    ctx.state_mut::<Vec<String>>().unwrap().push(line);
+    Ok(())
+}
+
+fn run(harness: Harness, args: &[&str]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    harness
+        .run_with_state(&mut CloudApp::default(), &mut out, args.to_vec())
+        .assert_success();
+    out
+}
+
+#[test]
+fn test_prefixed_env_vars() {
+    let harness = Harness::new()
+        .env("CLOUD_CLI_TOKEN", "abc")
+        .env("LOG_LEVEL", "debug")
+        // An explicit `env` is kept, so this one is ignored
+        .env("CLOUD_CLI_LOG_LEVEL", "trace");
+    assert_eq!(run(harness, &["cloud"]), vec!["root abc debug"]);
+}
+
+#[test]
+fn test_subcommand_env_vars() {
+    let harness = Harness::new()
+        .env("CLOUD_CLI_DEPLOY_REGION", "eu-1")
+        .env("CLOUD_CLI_REGION", "ignored");
+    assert_eq!(run(harness, &["cloud", "deploy"]), vec!["deploy eu-1"]);
+}
+
+#[test]
+fn test_help_shows_env_names() {
+    let help = generate_help(&CloudApp::default());
+    assert!(help.contains("[env: CLOUD_CLI_TOKEN]"));
+    assert!(help.contains("[env: LOG_LEVEL]"));
+}
+
+#[test]
+fn test_man_page_environment_section() {
+    let man = generate_man_page(&CloudApp::default(), "2024-01-01");
+    let env = &man[man.find(".SH ENVIRONMENT").expect("missing ENVIRONMENT")..];
+    assert!(env.contains("\\fBCLOUD_CLI_TOKEN\\fR\nDefault for \\fB--token\\fR."));
+    assert!(env.contains("\\fBLOG_LEVEL\\fR"));
+    assert!(env.contains(
+        "\\fBCLOUD_CLI_DEPLOY_REGION\\fR\nDefault for \\fB--region\\fR of \\fBdeploy\\fR."
+    ));
+}
@@ -0,0 +1,170 @@
+#![cfg(unix)]
+
+use koral::completion::{generate_to, Shell};
+use koral::external::ExternalCommand;
+use koral::help::generate_help;
+use koral::prelude::*;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", default = "us-east-1")]
+struct RegionFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "status")]
+struct StatusCmd;
+
+#[derive(Subcommand)]
+enum OpsCommands {
+    Status(StatusCmd),
+}
+
+impl Default for OpsCommands {
+    fn default() -> Self {
+        Self::Status(StatusCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "kext", env_prefix = "KEXT", strict, external_subcommands)]
+#[app(flags(RegionFlag))]
+struct OpsApp {
+    #[app(subcommand)]
+    cmd: OpsCommands,
+}
+
+fn record(cmd: ExternalCommand, out: State<Arc<Mutex<Vec<String>>>>) -> KoralResult<()> {
+    out.lock()
+        .unwrap()
+        .push(format!("{} {:?}", cmd.name, cmd.args));
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "khandled", external_subcommands = record)]
+#[app(flags(RegionFlag))]
+struct HandledApp;
+
+// Puts `kext-<name>` plugins on PATH once for every test
+fn plugin_dir() -> &'static PathBuf {
+    static DIR: OnceLock<PathBuf> = OnceLock::new();
+    DIR.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("koral-external-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let scripts = [
+            (
+                "kext-hello",
+                "#!/bin/sh\nprintf '%s|%s\\n' \"$*\" \"$KEXT_REGION\" > \"$(dirname \"$0\")/hello.out\"\n",
+            ),
+            ("kext-broken", "#!/bin/sh\nexit 3\n"),
+        ];
+        for (name, body) in scripts {
+            let path = dir.join(name);
+            std::fs::write(&path, body).unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        // Not executable, so not a plugin
+        std::fs::write(dir.join("kext-notes"), "").unwrap();
+
+        let path = std::env::var_os("PATH").unwrap_or_default();
+        let mut paths = vec![dir.clone()];
+        paths.extend(std::env::split_paths(&path));
+        std::env::set_var("PATH", std::env::join_paths(paths).unwrap());
+        dir
+    })
+}
+
+fn run(args: &[&str]) -> KoralResult<()> {
+    plugin_dir();
+    OpsApp::default().run(args.iter().map(|s| s.to_string()).collect())
+}
+
+#[test]
+fn test_unknown_subcommand_runs_plugin() {
+    run(&["kext", "--region", "eu-west-1", "hello", "a", "--flag", "b"]).unwrap();
+    let out = std::fs::read_to_string(plugin_dir().join("hello.out")).unwrap();
+    assert_eq!(out, "a --flag b|eu-west-1\n");
+}
+
+#[test]
+fn test_plugin_exit_status_is_reported() {
+    let err = run(&["kext", "broken"]).unwrap_err();
+    assert!(matches!(err, KoralError::ExternalCommandFailed(ref name, 3) if name == "broken"));
+}
+
+#[test]
+fn test_missing_plugin_is_unknown_subcommand() {
+    let err = run(&["kext", "nope"]).unwrap_err();
+    assert!(matches!(err, KoralError::InvalidSubcommand(ref msg) if msg.contains("'nope'")));
+    let err = run(&["kext", "notes"]).unwrap_err();
+    assert!(matches!(err, KoralError::InvalidSubcommand(_)));
+}
+
+#[test]
+fn test_builtin_subcommand_wins() {
+    run(&["kext", "status"]).unwrap();
+}
+
+#[test]
+fn test_handler_receives_external_command() {
+    let mut out = Arc::new(Mutex::new(Vec::<String>::new()));
+    HandledApp
+        .run_with_state(
+            &mut out,
+            [
+                "khandled", "--region", "x", "deploy", "--region", "y", "now",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        )
+        .unwrap();
+    let lines = out.lock().unwrap().clone();
+    assert_eq!(lines, vec!["deploy [\"--region\", \"y\", \"now\"]"]);
+}
+
+#[derive(Arg, Debug, PartialEq)]
+#[arg(name = "name")]
+struct NameArg(String);
+
+#[test]
+fn test_positional_args_are_not_plugins() {
+    plugin_dir();
+    let mut app = koral::App::new("kext")
+        .arg::<NameArg>()
+        .external_subcommands(true)
+        .action(|ctx| {
+            assert_eq!(ctx.arg::<NameArg>(), Some("bob".to_string()));
+            Ok(())
+        });
+    app.run(vec!["kext".to_string(), "bob".to_string()])
+        .unwrap();
+}
+
+#[test]
+fn test_plugins_are_only_searched_when_listed() {
+    plugin_dir();
+    let app = OpsApp::default();
+    assert!(app.subcommands().iter().all(|s| !s.external));
+    let all = koral::external::with_plugins(&app);
+    assert!(all.iter().any(|s| s.name == "hello" && s.external));
+}
+
+#[test]
+fn test_help_and_completion_list_plugins() {
+    plugin_dir();
+    let help = generate_help(&OpsApp::default());
+    let heading = help.find("External Commands:").expect("missing heading");
+    assert!(help[heading..].contains("broken"));
+    assert!(help[heading..].contains("hello"));
+    assert!(!help.contains("notes"));
+    assert!(help[..heading].contains("status"));
+
+    let mut buf = Vec::new();
+    generate_to(&OpsApp::default(), Shell::Bash, &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("hello"));
+    assert!(output.contains("broken"));
+}
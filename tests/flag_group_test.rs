@@ -0,0 +1,123 @@
+use koral::help::generate_help;
+use koral::prelude::*;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "json", negatable, conflicts_with = "yaml")]
+struct JsonFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "yaml")]
+struct YamlFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "output", requires = "json")]
+struct OutputFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "id", value_name = "ID")]
+struct IdFlag(u32);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "name", value_name = "NAME")]
+struct NameFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "show", action = show)]
+#[app(flags(JsonFlag, YamlFlag, OutputFlag, IdFlag, NameFlag))]
+#[app(group(name = "target", flags(IdFlag, NameFlag), required, multiple = false))]
+struct ShowApp;
+
+fn show(_ctx: Context) -> KoralResult<()> {
+    Ok(())
+}
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "xml", negatable)]
+struct XmlFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "csv")]
+struct CsvFlag(bool);
+
+#[derive(FlagGroup)]
+#[group(flags(XmlFlag, CsvFlag), multiple = false)]
+struct FormatGroup;
+
+#[derive(Default, App)]
+#[app(name = "fmt", action = show)]
+#[app(flags(XmlFlag, CsvFlag))]
+#[app(groups(FormatGroup))]
+struct FormatApp;
+
+fn run(app: &mut impl AppTrait, args: &[&str]) -> KoralResult<()> {
+    app.run(args.iter().map(|s| s.to_string()).collect())
+}
+
+#[test]
+fn test_conflicting_flags_are_rejected() {
+    let err = run(&mut ShowApp, &["show", "--id", "1", "--json", "--yaml"]).unwrap_err();
+    match err {
+        KoralError::Validation(msg) => {
+            assert!(msg.contains("'--json' cannot be used with '--yaml'"))
+        }
+        _ => panic!("Expected Validation error, got {:?}", err),
+    }
+    assert!(run(&mut ShowApp, &["show", "--id", "1", "--json"]).is_ok());
+}
+
+#[test]
+fn test_requires_other_flag() {
+    let err = run(&mut ShowApp, &["show", "--id", "1", "--output", "x"]).unwrap_err();
+    assert!(matches!(err, KoralError::MissingArgument(ref m) if m.contains("requires '--json'")));
+    assert!(run(
+        &mut ShowApp,
+        &["show", "--id", "1", "--output", "x", "--json"]
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_required_exclusive_group() {
+    let err = run(&mut ShowApp, &["show"]).unwrap_err();
+    match err {
+        KoralError::MissingArgument(msg) => {
+            assert!(msg.contains("One of '--id', '--name' is required"))
+        }
+        _ => panic!("Expected MissingArgument error, got {:?}", err),
+    }
+
+    let err = run(&mut ShowApp, &["show", "--id", "1", "--name", "x"]).unwrap_err();
+    assert!(matches!(err, KoralError::Validation(ref m) if m.contains("group 'target'")));
+
+    assert!(run(&mut ShowApp, &["show", "--name", "x"]).is_ok());
+}
+
+#[test]
+fn test_derived_group() {
+    assert_eq!(<FormatGroup as FlagGroup>::name(), "format");
+    assert_eq!(<FormatGroup as FlagGroup>::flags(), vec!["xml", "csv"]);
+    let err = run(&mut FormatApp, &["fmt", "--csv", "--xml"]).unwrap_err();
+    assert!(matches!(err, KoralError::Validation(ref m) if m.contains("group 'format'")));
+    assert!(run(&mut FormatApp, &["fmt"]).is_ok());
+}
+
+#[test]
+fn test_switched_off_flags_are_not_in_use() {
+    for json in ["--no-json", "--json=false"] {
+        assert!(run(&mut ShowApp, &["show", "--id", "1", json, "--yaml"]).is_ok());
+        let err = run(&mut ShowApp, &["show", "--id", "1", "--output", "x", json]).unwrap_err();
+        assert!(
+            matches!(err, KoralError::MissingArgument(ref m) if m.contains("requires '--json'"))
+        );
+    }
+    assert!(run(&mut FormatApp, &["fmt", "--no-xml", "--csv"]).is_ok());
+}
+
+#[test]
+fn test_help_renders_groups() {
+    let help = generate_help(&ShowApp);
+    assert!(help.contains("<--id <ID>|--name <NAME>>"));
+
+    let help = generate_help(&FormatApp);
+    assert!(help.contains("[--xml|--csv]"));
+}
@@ -0,0 +1,121 @@
+use koral::completion::{generate_to, Shell};
+use koral::help::generate_help;
+use koral::prelude::*;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "verbose", short = 'v', global, help = "Enable verbose output")]
+struct VerboseFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "output", short = 'o', default = "text", global)]
+struct OutputFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "force", short = 'f')]
+struct ForceFlag(bool);
+
+fn record(mut ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "verbose={} output={} ({})",
+        ctx.get::<VerboseFlag>().unwrap_or_default(),
+        ctx.get::<OutputFlag>().unwrap_or_default(),
+        ctx.source_of("output").unwrap()
+    );
+    ctx.state_mut::<Vec<String>>().unwrap().push(line);
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "list", action = record, strict)]
+struct ListCmd;
+
+#[derive(Subcommand)]
+enum ClusterCommands {
+    List(ListCmd),
+}
+
+impl Default for ClusterCommands {
+    fn default() -> Self {
+        Self::List(ListCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "clusters")]
+struct ClustersCmd {
+    #[app(subcommand)]
+    cmd: ClusterCommands,
+}
+
+#[derive(Default, App)]
+#[app(name = "deploy", action = record, strict)]
+#[app(flags(ForceFlag))]
+struct DeployCmd;
+
+#[derive(Subcommand)]
+enum OpsCommands {
+    Clusters(ClustersCmd),
+    Deploy(DeployCmd),
+}
+
+impl Default for OpsCommands {
+    fn default() -> Self {
+        Self::Deploy(DeployCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "ops", action = record)]
+#[app(flags(VerboseFlag, OutputFlag))]
+struct OpsApp {
+    #[app(subcommand)]
+    cmd: OpsCommands,
+}
+
+fn run(args: &[&str]) -> KoralResult<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    OpsApp::default().run_with_state(&mut out, args.iter().map(|s| s.to_string()).collect())?;
+    Ok(out)
+}
+
+#[test]
+fn test_global_value_reaches_nested_subcommand() {
+    let out = run(&["ops", "-o", "json", "clusters", "list", "--verbose"]).unwrap();
+    assert_eq!(out, vec!["verbose=true output=json (command line)"]);
+
+    let out = run(&["ops", "clusters", "list"]).unwrap();
+    assert_eq!(out, vec!["verbose=false output=text (default)"]);
+}
+
+#[test]
+fn test_strict_subcommand_accepts_global_flag() {
+    // The root cannot split `-vf`, so the subcommand parses it
+    let out = run(&["ops", "deploy", "-vf"]).unwrap();
+    assert_eq!(out, vec!["verbose=true output=text (default)"]);
+
+    let err = run(&["ops", "deploy", "-xf"]).unwrap_err();
+    assert!(matches!(err, KoralError::UnknownFlag(_)));
+}
+
+#[test]
+fn test_global_options_help_heading() {
+    let help = generate_help(&OpsApp::default());
+    let heading = help.find("Global Options:").expect("missing heading");
+    assert!(help[heading..].contains("--verbose"));
+    assert!(help[heading..].contains("--output"));
+}
+
+#[test]
+fn test_completion_lists_globals_at_every_level() {
+    let mut buf = Vec::new();
+    generate_to(&OpsApp::default(), Shell::Fish, &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("-n '__fish_seen_subcommand_from list' -s v -l verbose"));
+    assert!(output.contains("-n '__fish_seen_subcommand_from deploy' -s o -l output"));
+
+    let mut buf = Vec::new();
+    generate_to(&OpsApp::default(), Shell::Bash, &mut buf).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("opts=\"--verbose -v --output -o\""));
+    assert!(output.contains("opts=\"--force -f --verbose -v --output -o\""));
+}
@@ -14,6 +14,9 @@ impl Flag for HeaderFlag {
     fn takes_value() -> bool {
         true
     }
+    fn from_values(values: &[&str]) -> Result<String, String> {
+        String::from_last(values)
+    }
 }
 
 #[test]
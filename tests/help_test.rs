@@ -18,7 +18,6 @@ fn run(_ctx: Context) -> KoralResult<()> {
 fn test_help_contains_description() {
     let app = HelperApp;
     let help = generate_help(&app);
-    std::fs::write("help_debug.txt", &help).unwrap();
     assert!(
         help.contains("A helpful app"),
         "Help should contain description"
@@ -0,0 +1,181 @@
+use koral::prelude::*;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "verbose")]
+struct VerboseFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "version-file")]
+struct VersionFileFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "dry")]
+struct DryFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "dry-run")]
+struct DryRunFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "namespace", aliases = "ns")]
+struct NamespaceFlag(String);
+
+fn record(mut ctx: Context) -> KoralResult<()> {
+    let mut flags: Vec<String> = ctx
+        .flags
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v.clone().unwrap_or_default()))
+        .collect();
+    flags.sort();
+    let line = flags.join(" ");
+    ctx.state_mut::<Vec<String>>().unwrap().push(line);
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "list", action = list)]
+#[app(flags(NamespaceFlag))]
+struct ListCmd;
+
+fn list(mut ctx: Context) -> KoralResult<()> {
+    let ns = ctx.get::<NamespaceFlag>().unwrap_or_default();
+    ctx.state_mut::<Vec<String>>()
+        .unwrap()
+        .push(format!("list {}", ns));
+    Ok(())
+}
+
+#[derive(Subcommand)]
+enum ClusterCommands {
+    List(ListCmd),
+}
+
+impl Default for ClusterCommands {
+    fn default() -> Self {
+        Self::List(ListCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "clusters")]
+struct ClustersCmd {
+    #[app(subcommand)]
+    cmd: ClusterCommands,
+}
+
+#[derive(Default, App)]
+#[app(name = "config", action = config)]
+struct ConfigCmd;
+
+fn config(mut ctx: Context) -> KoralResult<()> {
+    ctx.state_mut::<Vec<String>>()
+        .unwrap()
+        .push("config".to_string());
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "certs", action = certs)]
+struct CertsCmd;
+
+fn certs(mut ctx: Context) -> KoralResult<()> {
+    ctx.state_mut::<Vec<String>>()
+        .unwrap()
+        .push("certs".to_string());
+    Ok(())
+}
+
+#[derive(Subcommand)]
+enum OpsCommands {
+    Clusters(ClustersCmd),
+    Config(ConfigCmd),
+    Certs(CertsCmd),
+}
+
+impl Default for OpsCommands {
+    fn default() -> Self {
+        Self::Config(ConfigCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "ops", action = record, infer_long_args, infer_subcommands)]
+#[app(flags(VerboseFlag, VersionFileFlag, DryFlag, DryRunFlag))]
+struct OpsApp {
+    #[app(subcommand)]
+    cmd: OpsCommands,
+}
+
+#[derive(Default, App)]
+#[app(name = "strictops", action = record)]
+#[app(flags(VerboseFlag, VersionFileFlag))]
+struct ExactApp {
+    #[app(subcommand)]
+    cmd: OpsCommands,
+}
+
+fn run(app: &mut impl koral::traits::App, args: &[&str]) -> KoralResult<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    app.run_with_state(&mut out, args.iter().map(|s| s.to_string()).collect())?;
+    Ok(out)
+}
+
+#[test]
+fn test_long_flag_prefix() {
+    let out = run(
+        &mut OpsApp::default(),
+        &["ops", "--verb", "--version-f=v.txt"],
+    )
+    .unwrap();
+    assert_eq!(out, vec!["verbose= version-file=v.txt"]);
+}
+
+#[test]
+fn test_exact_name_wins_over_prefix() {
+    let out = run(&mut OpsApp::default(), &["ops", "--dry"]).unwrap();
+    assert_eq!(out, vec!["dry="]);
+}
+
+#[test]
+fn test_ambiguous_long_flag() {
+    let err = run(&mut OpsApp::default(), &["ops", "--ver"]).unwrap_err();
+    match err {
+        KoralError::InvalidFlag(msg) => {
+            assert!(msg.contains("Flag '--ver' is ambiguous"));
+            assert!(msg.contains("[candidates: --verbose, --version-file]"));
+        }
+        other => panic!("Expected InvalidFlag, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_subcommand_prefix_at_every_level() {
+    let out = run(
+        &mut OpsApp::default(),
+        &["ops", "clu", "li", "--names", "prod"],
+    )
+    .unwrap();
+    assert_eq!(out, vec!["list prod"]);
+
+    let out = run(&mut OpsApp::default(), &["ops", "con"]).unwrap();
+    assert_eq!(out, vec!["config"]);
+}
+
+#[test]
+fn test_ambiguous_subcommand() {
+    let err = run(&mut OpsApp::default(), &["ops", "c"]).unwrap_err();
+    match err {
+        KoralError::InvalidSubcommand(msg) => {
+            assert!(msg.contains("Command 'c' is ambiguous"));
+            assert!(msg.contains("[candidates: clusters, config, certs]"));
+        }
+        other => panic!("Expected InvalidSubcommand, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_inference_is_opt_in() {
+    let out = run(&mut ExactApp::default(), &["strictops", "--verb", "con"]).unwrap();
+    // Unknown flag and command stay positional arguments
+    assert_eq!(out, vec![""]);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use koral::help::generate_help;
+use koral::prelude::*;
+
+#[derive(Flag, Debug, Clone, PartialEq)]
+#[flag(name = "tag", short = 't', help = "Tag to apply")]
+struct TagFlag(Vec<String>);
+
+#[derive(Flag, Debug, Clone, PartialEq)]
+#[flag(name = "port", value_delimiter = ',', default = "80,443")]
+struct PortFlag(Vec<u16>);
+
+#[derive(Flag, Debug, Clone, PartialEq)]
+#[flag(name = "name")]
+struct NameFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "multi")]
+#[app(flags(TagFlag, PortFlag, NameFlag))]
+struct MultiApp;
+
+fn parse(args: &[&str]) -> KoralResult<Context<'static>> {
+    common::parse(&MultiApp, args)
+}
+
+#[test]
+fn test_repeated_flag_collects_values() {
+    let ctx = parse(&["--tag", "a", "-t", "b", "--tag=c", "-td"]).unwrap();
+    assert_eq!(
+        ctx.get::<TagFlag>(),
+        Some(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+            "d".to_string()
+        ])
+    );
+}
+
+#[test]
+fn test_value_delimiter_splits_values() {
+    let ctx = parse(&["--port", "8080,8081", "--port", "9000"]).unwrap();
+    assert_eq!(ctx.get::<PortFlag>(), Some(vec![8080, 8081, 9000]));
+}
+
+#[test]
+fn test_multiple_default_is_split() {
+    let ctx = parse(&[]).unwrap();
+    assert_eq!(ctx.get::<PortFlag>(), Some(vec![80, 443]));
+    assert_eq!(ctx.get::<TagFlag>(), None);
+}
+
+#[test]
+fn test_single_valued_flag_keeps_last() {
+    let ctx = parse(&["--name", "first", "--name", "second"]).unwrap();
+    assert_eq!(ctx.get::<NameFlag>(), Some("second".to_string()));
+}
+
+#[test]
+fn test_invalid_element_is_rejected() {
+    let ctx = parse(&["--port", "80,http"]).unwrap();
+    assert_eq!(ctx.get::<PortFlag>(), None);
+}
+
+#[test]
+fn test_flag_arg_extracts_vec() {
+    let ctx = parse(&["-t", "x", "-t", "y"]).unwrap();
+    let tags = FlagArg::<TagFlag>::from_context(&ctx).unwrap();
+    assert_eq!(*tags, vec!["x".to_string(), "y".to_string()]);
+}
+
+#[test]
+fn test_help_marks_repeatable_flags() {
+    let help = generate_help(&MultiApp);
+    assert!(help.contains("<value>..."));
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use koral::completion::{generate_to, Shell};
+use koral::help::generate_help;
+use koral::prelude::*;
+use koral::testing::Harness;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(
+    name = "color",
+    short = 'c',
+    negatable,
+    default = "true",
+    help = "Colorize output"
+)]
+struct ColorFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(
+    name = "pager",
+    negatable,
+    env = "KORAL_TEST_PAGER",
+    conflicts_with = "quiet"
+)]
+struct PagerFlag(bool);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "quiet")]
+struct QuietFlag(bool);
+
+#[derive(Default, App)]
+#[app(name = "neg", strict, action = report)]
+#[app(flags(ColorFlag, PagerFlag, QuietFlag))]
+struct NegApp;
+
+fn report(ctx: Context) -> KoralResult<()> {
+    ctx.terminal()
+        .print(&format!("pager={:?}\n", ctx.get::<PagerFlag>()))
+}
+
+fn parse(args: &[&str]) -> KoralResult<Context<'static>> {
+    common::parse_with(&common::parser(&NegApp).strict(true), args)
+}
+
+#[test]
+fn test_no_prefix_overrides_default() {
+    assert_eq!(parse(&[]).unwrap().get::<ColorFlag>(), Some(true));
+    assert_eq!(
+        parse(&["--no-color"]).unwrap().get::<ColorFlag>(),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_explicit_boolean_value() {
+    assert_eq!(
+        parse(&["--color=false"]).unwrap().get::<ColorFlag>(),
+        Some(false)
+    );
+    assert_eq!(
+        parse(&["--no-color", "--color=TRUE"])
+            .unwrap()
+            .get::<ColorFlag>(),
+        Some(true)
+    );
+    assert!(parse(&["--color=maybe"]).is_err());
+    assert!(parse(&["--no-color=true"]).is_err());
+}
+
+#[test]
+fn test_last_occurrence_wins() {
+    assert_eq!(
+        parse(&["--no-color", "-c"]).unwrap().get::<ColorFlag>(),
+        Some(true)
+    );
+    assert_eq!(
+        parse(&["-c", "--no-color"]).unwrap().get::<ColorFlag>(),
+        Some(false)
+    );
+}
+
+#[test]
+fn test_no_prefix_overrides_env() {
+    let harness = Harness::new().env("KORAL_TEST_PAGER", "1");
+    harness
+        .run(&mut NegApp, ["neg"])
+        .assert_stdout_contains("pager=Some(true)");
+    harness
+        .run(&mut NegApp, ["neg", "--no-pager"])
+        .assert_stdout_contains("pager=Some(false)");
+}
+
+#[test]
+fn test_negated_flag_does_not_conflict() {
+    assert!(matches!(
+        parse(&["--pager", "--quiet"]),
+        Err(KoralError::Validation(_))
+    ));
+    assert!(parse(&["--no-pager", "--quiet"]).is_ok());
+    assert!(parse(&["--pager=false", "--quiet"]).is_ok());
+}
+
+#[test]
+fn test_non_negatable_flag_rejects_no_prefix() {
+    let err = parse(&["--no-quiet"]).unwrap_err();
+    assert!(matches!(err, KoralError::UnknownFlag(_)));
+    assert!(parse(&["--quiet=false"]).is_err());
+}
+
+#[test]
+fn test_help_and_completion_list_negation() {
+    let help = generate_help(&NegApp);
+    assert!(help.contains("--no-color"));
+    assert!(!help.contains("--no-quiet"));
+
+    let man = koral::man::generate_man_page(&NegApp, "2024-01-01");
+    assert!(man.contains("--no-pager"));
+
+    for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+        let mut buf = Vec::new();
+        generate_to(&NegApp, shell, &mut buf).unwrap();
+        let script = String::from_utf8(buf).unwrap();
+        assert!(
+            script.contains("no-color"),
+            "{:?} missing --no-color",
+            shell
+        );
+    }
+}
@@ -11,7 +11,7 @@ fn run(_ctx: Context) -> KoralResult<()> {
 #[test]
 fn test_negative_number_as_positional() {
     let app = NegApp;
-    let args = vec![
+    let args = [
         "neg_test".to_string(),
         "-100".to_string(),
         "-5.5".to_string(),
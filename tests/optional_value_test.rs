@@ -0,0 +1,63 @@
+mod common;
+
+use koral::completion::{generate_to, Shell};
+use koral::help::generate_help;
+use koral::prelude::*;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(
+    name = "color",
+    short = 'c',
+    missing_value = "always",
+    default = "auto",
+    value_name = "when",
+    help = "When to use colors"
+)]
+struct ColorFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "opt")]
+#[app(flags(ColorFlag))]
+struct OptApp;
+
+fn parse(args: &[&str]) -> KoralResult<Context<'static>> {
+    common::parse(&OptApp, args)
+}
+
+#[test]
+fn test_absent_flag_uses_default() {
+    let ctx = parse(&[]).unwrap();
+    assert_eq!(ctx.get::<ColorFlag>(), Some("auto".to_string()));
+}
+
+#[test]
+fn test_bare_flag_uses_missing_value() {
+    let ctx = parse(&["--color", "file.txt"]).unwrap();
+    assert_eq!(ctx.get::<ColorFlag>(), Some("always".to_string()));
+    assert_eq!(ctx.args, vec!["file.txt".to_string()]);
+
+    let ctx = parse(&["-c", "file.txt"]).unwrap();
+    assert_eq!(ctx.get::<ColorFlag>(), Some("always".to_string()));
+    assert_eq!(ctx.args, vec!["file.txt".to_string()]);
+}
+
+#[test]
+fn test_attached_value_is_used() {
+    let ctx = parse(&["--color=never", "file.txt"]).unwrap();
+    assert_eq!(ctx.get::<ColorFlag>(), Some("never".to_string()));
+    assert_eq!(ctx.args, vec!["file.txt".to_string()]);
+
+    let ctx = parse(&["-cnever"]).unwrap();
+    assert_eq!(ctx.get::<ColorFlag>(), Some("never".to_string()));
+}
+
+#[test]
+fn test_help_and_completion_mark_optional_value() {
+    let help = generate_help(&OptApp);
+    assert!(help.contains("[=<when>]"));
+
+    let mut buf = Vec::new();
+    generate_to(&OptApp, Shell::Zsh, &mut buf).unwrap();
+    let zsh = String::from_utf8(buf).unwrap();
+    assert!(zsh.contains("'--color=-[When to use colors]::when'"));
+}
@@ -0,0 +1,103 @@
+use koral::output::{run_with_terminal, Buffer};
+use koral::prelude::*;
+use koral::repl::{LineReader, Repl};
+use std::io::Write;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(
+    name = "name",
+    default = "World",
+    help = "Who to greet, written out in full so that the help text wraps"
+)]
+struct NameFlag(String);
+
+fn greet(name: FlagArg<NameFlag>, term: Terminal) -> KoralResult<()> {
+    writeln!(term.stdout(), "Hello, {}!", *name)?;
+    writeln!(term.stderr(), "greeted")?;
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "hello", action = greet)]
+#[app(flags(NameFlag))]
+struct HelloCmd;
+
+#[derive(Subcommand)]
+enum Commands {
+    Hello(HelloCmd),
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::Hello(HelloCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "greeter", version = "1.2.3", description = "Greets people")]
+struct Greeter {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+fn capture(terminal: impl FnOnce(Terminal) -> Terminal, args: &[&str]) -> (String, String) {
+    let (stdout, stderr) = (Buffer::new(), Buffer::new());
+    let term = terminal(Terminal::new(stdout.clone(), stderr.clone()));
+    let args = args.iter().map(|s| s.to_string()).collect();
+    run_with_terminal(&mut Greeter::default(), term, None, args).unwrap();
+    (stdout.contents(), stderr.contents())
+}
+
+#[test]
+fn test_help_and_version_are_captured() {
+    let (help, _) = capture(|t| t, &["greeter", "--help"]);
+    assert!(help.starts_with("Usage: greeter"));
+    assert!(help.contains("Greets people"));
+    assert!(!help.contains('\x1b'));
+
+    let stdout = Buffer::new();
+    let mut app = App::new("builder").version("1.2.3");
+    let term = Terminal::new(stdout.clone(), Buffer::new());
+    run_with_terminal(&mut app, term, None, vec!["builder".into(), "-V".into()]).unwrap();
+    assert_eq!(stdout.contents(), "builder version 1.2.3\n");
+}
+
+#[test]
+fn test_help_follows_color_and_width() {
+    let (narrow, _) = capture(|t| t.with_width(40), &["greeter", "hello", "--help"]);
+    let (wide, _) = capture(|t| t.with_width(200), &["greeter", "hello", "--help"]);
+    assert!(narrow.lines().count() > wide.lines().count());
+
+    let (colored, _) = capture(|t| t.with_color(true), &["greeter", "--help"]);
+    assert!(colored.contains('\x1b'));
+}
+
+#[test]
+fn test_handlers_write_to_the_terminal() {
+    let (stdout, stderr) = capture(|t| t, &["greeter", "hello", "--name", "Ada"]);
+    assert_eq!(stdout, "Hello, Ada!\n");
+    assert_eq!(stderr, "greeted\n");
+}
+
+struct Lines(Vec<&'static str>);
+
+impl LineReader for Lines {
+    fn read_line(&mut self, _prompt: &str) -> KoralResult<Option<String>> {
+        Ok((!self.0.is_empty()).then(|| self.0.remove(0).to_string()))
+    }
+}
+
+#[test]
+fn test_repl_reports_errors_on_the_terminal() {
+    let (stdout, stderr) = (Buffer::new(), Buffer::new());
+    let mut app = Greeter::default();
+    Repl::new(&mut app)
+        .reader(Lines(vec!["hello --name", "hello"]))
+        .history_file(None)
+        .terminal(Terminal::new(stdout.clone(), stderr.clone()))
+        .run()
+        .unwrap();
+    assert_eq!(stdout.contents(), "Hello, World!\n");
+    assert!(stderr.contents().starts_with("Error: "));
+    assert!(stderr.contents().ends_with("greeted\n"));
+}
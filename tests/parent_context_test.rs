@@ -0,0 +1,173 @@
+use koral::prelude::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Session {
+    user: String,
+}
+
+#[derive(Default)]
+struct AuthMiddleware;
+
+impl Middleware for AuthMiddleware {
+    fn before(&self, ctx: &mut Context) -> KoralResult<()> {
+        ctx.insert_extension(Session {
+            user: "alice".to_string(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", default = "us-east-1")]
+struct RegionFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "namespace", short = 'n')]
+struct NamespaceFlag(String);
+
+fn list(
+    session: Extension<Session>,
+    region: ParentFlag<RegionFlag>,
+    namespace: Option<ParentFlag<NamespaceFlag>>,
+    out: State<Arc<Mutex<Vec<String>>>>,
+) -> KoralResult<()> {
+    out.lock().unwrap().push(format!(
+        "{} {} {:?}",
+        session.user,
+        *region,
+        namespace.map(|n| n.0)
+    ));
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "list", action = list)]
+struct ListCmd;
+
+#[derive(Subcommand)]
+enum ClusterCommands {
+    List(ListCmd),
+}
+
+impl Default for ClusterCommands {
+    fn default() -> Self {
+        Self::List(ListCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "clusters")]
+#[app(flags(NamespaceFlag))]
+struct ClustersCmd {
+    #[app(subcommand)]
+    cmd: ClusterCommands,
+}
+
+#[derive(Subcommand)]
+enum K8sCommands {
+    Clusters(ClustersCmd),
+}
+
+impl Default for K8sCommands {
+    fn default() -> Self {
+        Self::Clusters(ClustersCmd::default())
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "k8s", middleware(AuthMiddleware))]
+#[app(flags(RegionFlag))]
+struct K8sApp {
+    #[app(subcommand)]
+    cmd: K8sCommands,
+}
+
+fn run(args: &[&str]) -> Vec<String> {
+    let mut out = Arc::new(Mutex::new(Vec::new()));
+    K8sApp::default()
+        .run_with_state(&mut out, args.iter().map(|s| s.to_string()).collect())
+        .unwrap();
+    let lines = out.lock().unwrap().clone();
+    lines
+}
+
+#[test]
+fn test_child_extracts_parent_extension_and_flags() {
+    let out = run(&[
+        "k8s",
+        "--region",
+        "eu-west-1",
+        "clusters",
+        "-n",
+        "prod",
+        "list",
+    ]);
+    assert_eq!(out, vec!["alice eu-west-1 Some(\"prod\")"]);
+}
+
+#[test]
+fn test_parent_flag_falls_back_to_default() {
+    let out = run(&["k8s", "clusters", "list"]);
+    assert_eq!(out, vec!["alice us-east-1 None"]);
+}
+
+fn inspect(ctx: Context) -> KoralResult<()> {
+    let parent = ctx.parent().expect("list has a parent");
+    let root = parent.parent().expect("clusters has a parent");
+    assert_eq!(parent.value_of("namespace"), Some("dev"));
+    assert_eq!(root.value_of("region"), Some("ap-1"));
+    assert!(root.parent().is_none());
+    assert!(parent.get_extension::<Session>().is_some());
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "list", action = inspect)]
+struct InspectCmd;
+
+#[derive(Subcommand)]
+enum InspectClusterCommands {
+    List(InspectCmd),
+}
+
+impl Default for InspectClusterCommands {
+    fn default() -> Self {
+        Self::List(InspectCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "clusters")]
+#[app(flags(NamespaceFlag))]
+struct InspectClustersCmd {
+    #[app(subcommand)]
+    cmd: InspectClusterCommands,
+}
+
+#[derive(Subcommand)]
+enum InspectCommands {
+    Clusters(InspectClustersCmd),
+}
+
+impl Default for InspectCommands {
+    fn default() -> Self {
+        Self::Clusters(InspectClustersCmd::default())
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "k8s", middleware(AuthMiddleware))]
+#[app(flags(RegionFlag))]
+struct InspectApp {
+    #[app(subcommand)]
+    cmd: InspectCommands,
+}
+
+#[test]
+fn test_parent_chain_reaches_root() {
+    let args = ["k8s", "--region", "ap-1", "clusters", "-n", "dev", "list"];
+    InspectApp::default()
+        .run(args.iter().map(|s| s.to_string()).collect())
+        .unwrap();
+}
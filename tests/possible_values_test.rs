@@ -0,0 +1,90 @@
+mod common;
+
+use koral::completion::{generate_to, Shell};
+use koral::help::generate_help;
+use koral::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, FlagValue)]
+enum Format {
+    Json,
+    Text,
+}
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "format", short = 'f', default = "text", help = "Output format")]
+struct FormatFlag(Format);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "level", possible_values = "debug, info, warn")]
+struct LevelFlag(Vec<String>);
+
+#[derive(Default, App)]
+#[app(name = "pv")]
+#[app(flags(FormatFlag, LevelFlag))]
+struct PvApp;
+
+fn parse(args: &[&str]) -> KoralResult<Context<'static>> {
+    common::parse(&PvApp, args)
+}
+
+#[test]
+fn test_possible_values_from_enum_and_attribute() {
+    assert_eq!(
+        <FormatFlag as Flag>::possible_values(),
+        vec!["json", "text"]
+    );
+    assert_eq!(
+        <LevelFlag as Flag>::possible_values(),
+        vec!["debug", "info", "warn"]
+    );
+}
+
+#[test]
+fn test_accepts_listed_values() {
+    let ctx = parse(&["--format", "JSON", "--level", "info", "--level", "warn"]).unwrap();
+    assert_eq!(ctx.get::<FormatFlag>(), Some(Format::Json));
+    assert_eq!(
+        ctx.get::<LevelFlag>(),
+        Some(vec!["info".to_string(), "warn".to_string()])
+    );
+}
+
+#[test]
+fn test_rejects_unknown_value_with_suggestion() {
+    let err = parse(&["--format", "jsn"]).unwrap_err();
+    match err {
+        KoralError::Validation(msg) => {
+            assert!(msg.contains("Invalid value 'jsn' for flag '--format'"));
+            assert!(msg.contains("[possible values: json, text]"));
+            assert!(msg.contains("Did you mean 'json'?"));
+        }
+        _ => panic!("Expected Validation error, got {:?}", err),
+    }
+
+    assert!(parse(&["--level", "info", "--level", "trace"]).is_err());
+}
+
+#[test]
+fn test_help_lists_possible_values() {
+    let help = generate_help(&PvApp);
+    assert!(help.contains("Output format [possible values: json, text]"));
+}
+
+#[test]
+fn test_completion_offers_possible_values() {
+    let mut buf = Vec::new();
+    generate_to(&PvApp, Shell::Bash, &mut buf).unwrap();
+    let bash = String::from_utf8(buf).unwrap();
+    assert!(bash.contains("--format|-f)"));
+    assert!(bash.contains("compgen -W \"json text\""));
+
+    let mut buf = Vec::new();
+    generate_to(&PvApp, Shell::Zsh, &mut buf).unwrap();
+    let zsh = String::from_utf8(buf).unwrap();
+    assert!(zsh.contains(":value:(json text)"));
+
+    let mut buf = Vec::new();
+    generate_to(&PvApp, Shell::Fish, &mut buf).unwrap();
+    let fish = String::from_utf8(buf).unwrap();
+    assert!(fish.contains("-a 'debug info warn'"));
+}
@@ -0,0 +1,238 @@
+use koral::prelude::*;
+use koral::prompt::{run_with_io, PromptIo, TerminalIo};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, FlagValue)]
+enum Stage {
+    Dev,
+    Prod,
+}
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "token", help = "API token", required = true, sensitive)]
+struct TokenFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "stage", required = true)]
+struct StageFlag(Stage);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "force", help = "Overwrite the release", required = true)]
+struct ForceFlag(bool);
+
+fn record(mut ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "token={} stage={:?} force={} {}",
+        ctx.get::<TokenFlag>().unwrap(),
+        ctx.get::<StageFlag>().unwrap(),
+        ctx.get::<ForceFlag>().unwrap(),
+        ctx.describe("token").unwrap()
+    );
+    ctx.state_mut::<Vec<String>>().unwrap().push(line);
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "release", action = record)]
+#[app(flags(TokenFlag, StageFlag, ForceFlag))]
+struct ReleaseCmd;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "replicas", required = true)]
+struct ReplicasFlag(u32);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "note")]
+struct NoteFlag(String);
+
+fn record_scale(mut ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "replicas={} note={:?}",
+        ctx.get::<ReplicasFlag>().unwrap(),
+        ctx.get::<NoteFlag>()
+    );
+    ctx.state_mut::<Vec<String>>().unwrap().push(line);
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "scale", action = record_scale)]
+#[app(flags(ReplicasFlag, NoteFlag))]
+struct ScaleCmd;
+
+#[derive(Subcommand)]
+enum Commands {
+    Release(ReleaseCmd),
+    Scale(ScaleCmd),
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::Release(ReleaseCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "deployer", prompt_missing)]
+struct Deployer {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", required = true)]
+struct RegionFlag(String);
+
+#[derive(Default, App)]
+#[app(name = "regional", prompt_missing, infer_subcommands)]
+#[app(flags(RegionFlag))]
+struct Regional {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+/// Answers prompts from a script, recording what was shown.
+struct Script {
+    interactive: bool,
+    answers: VecDeque<String>,
+    transcript: Rc<RefCell<String>>,
+}
+
+impl PromptIo for Script {
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    fn write(&mut self, text: &str) -> KoralResult<()> {
+        self.transcript.borrow_mut().push_str(text);
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> KoralResult<Option<String>> {
+        Ok(self.answers.pop_front())
+    }
+
+    fn read_secret(&mut self) -> KoralResult<Option<String>> {
+        self.transcript.borrow_mut().push_str("<hidden>");
+        self.read_line()
+    }
+}
+
+fn run(interactive: bool, answers: &[&str], args: &[&str]) -> (KoralResult<Vec<String>>, String) {
+    let transcript = Rc::new(RefCell::new(String::new()));
+    let io = Script {
+        interactive,
+        answers: answers.iter().map(|s| s.to_string()).collect(),
+        transcript: transcript.clone(),
+    };
+    let mut seen: Vec<String> = Vec::new();
+    let mut full = vec!["deployer".to_string(), "release".to_string()];
+    full.extend(args.iter().map(|s| s.to_string()));
+    let result = run_with_io(&mut Deployer::default(), io, Some(&mut seen), full).map(|_| seen);
+    let transcript = transcript.borrow().clone();
+    (result, transcript)
+}
+
+#[test]
+fn test_missing_flags_are_prompted() {
+    let (result, transcript) = run(true, &["", "s3cret", "3", "PROD", "y"], &[]);
+    assert_eq!(
+        result.unwrap(),
+        vec!["token=s3cret stage=Prod force=true token=******** (from prompt)"]
+    );
+    assert_eq!(
+        transcript,
+        "API token (--token): <hidden>API token (--token): <hidden>\
+         --stage:\n  1) dev\n  2) prod\nSelect 1-2: '3' is not one of the choices\nSelect 1-2: \
+         Overwrite the release (--force)? [y/N] "
+    );
+}
+
+#[test]
+fn test_menu_accepts_numbers_and_confirm_defaults_to_no() {
+    let (result, _) = run(true, &["1", ""], &["--token", "t"]);
+    assert_eq!(
+        result.unwrap(),
+        vec!["token=t stage=Dev force=false token=******** (from command line)"]
+    );
+}
+
+#[test]
+fn test_no_input_keeps_missing_argument_error() {
+    let (result, transcript) = run(true, &["s3cret"], &["--no-input"]);
+    assert!(matches!(result, Err(KoralError::MissingArgument(ref msg)) if msg.contains("--token")));
+    assert!(transcript.is_empty());
+
+    let (result, transcript) = run(false, &["s3cret"], &[]);
+    assert!(matches!(result, Err(KoralError::MissingArgument(_))));
+    assert!(transcript.is_empty());
+}
+
+#[test]
+fn test_end_of_input_reports_missing_flag() {
+    let (result, _) = run(true, &["s3cret"], &[]);
+    assert!(matches!(result, Err(KoralError::MissingArgument(ref msg)) if msg.contains("--stage")));
+}
+
+#[test]
+fn test_dispatching_command_does_not_prompt() {
+    let (result, transcript) = run(true, &[], &["--token", "t", "--stage", "dev", "--force"]);
+    assert_eq!(result.unwrap().len(), 1);
+    assert!(transcript.is_empty());
+}
+
+#[test]
+fn test_abbreviated_subcommand_does_not_prompt_for_root_flags() {
+    let transcript = Rc::new(RefCell::new(String::new()));
+    let io = Script {
+        interactive: true,
+        answers: VecDeque::new(),
+        transcript: transcript.clone(),
+    };
+    let mut seen: Vec<String> = Vec::new();
+    let args = [
+        "regional", "rel", "--token", "t", "--stage", "dev", "--force",
+    ];
+    let args = args.iter().map(|s| s.to_string()).collect();
+    run_with_io(&mut Regional::default(), io, Some(&mut seen), args).unwrap();
+    assert_eq!(seen.len(), 1);
+    assert!(transcript.borrow().is_empty());
+}
+
+#[test]
+fn test_invalid_answer_is_asked_again() {
+    let transcript = Rc::new(RefCell::new(String::new()));
+    let io = Script {
+        interactive: true,
+        answers: ["many", "3"].iter().map(|s| s.to_string()).collect(),
+        transcript: transcript.clone(),
+    };
+    let mut seen: Vec<String> = Vec::new();
+    let args = ["deployer", "scale"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    run_with_io(&mut Deployer::default(), io, Some(&mut seen), args).unwrap();
+    assert_eq!(seen, vec!["replicas=3 note=None"]);
+
+    // The optional --note is never asked for
+    let transcript = transcript.borrow();
+    assert!(transcript.starts_with("--replicas: Invalid value: "));
+    assert!(transcript.ends_with("\n--replicas: "));
+    assert!(!transcript.contains("--note"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_secret_is_not_read_when_echo_stays_on() {
+    use std::io::IsTerminal;
+    // Echo cannot be turned off on input that is not a terminal
+    if !std::io::stdin().is_terminal() {
+        assert!(matches!(
+            TerminalIo.read_secret(),
+            Err(KoralError::IoError(_))
+        ));
+    }
+}
@@ -1,4 +1,5 @@
 use koral::prelude::*;
+use koral::provider::{EnvProvider, ValueProvider};
 use std::env;
 
 #[derive(Flag, Debug, PartialEq)]
@@ -39,3 +40,168 @@ fn test_env_provider() {
 
     env::remove_var("KORAL_TEST_ENV");
 }
+
+/// Stand-in for a secrets vault holding a fixed set of values.
+#[derive(Clone, Default)]
+struct VaultProvider;
+
+impl ValueProvider for VaultProvider {
+    fn get_value(&self, flag: &FlagDef) -> Option<String> {
+        match flag.name.as_str() {
+            "envflag" => Some("vault_value".to_string()),
+            "defaultflag" => Some("vault_default".to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ProfileProvider {
+    name: String,
+}
+
+impl ValueProvider for ProfileProvider {
+    fn get_value(&self, flag: &FlagDef) -> Option<String> {
+        (flag.name == "envflag").then(|| format!("profile_{}", self.name))
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "vault_first", action = capture)]
+#[app(flags(EnvFlag, DefaultFlag))]
+#[app(providers(VaultProvider, EnvProvider))]
+struct VaultFirstApp;
+
+#[derive(App)]
+#[app(name = "profile_app", action = capture)]
+#[app(flags(EnvFlag, DefaultFlag))]
+struct ProfileApp {
+    #[app(provider)]
+    profile: ProfileProvider,
+}
+
+impl Default for ProfileApp {
+    fn default() -> Self {
+        Self {
+            profile: ProfileProvider {
+                name: "dev".to_string(),
+            },
+        }
+    }
+}
+
+fn capture(mut ctx: Context) -> KoralResult<()> {
+    let values = [
+        ctx.get::<EnvFlag>().unwrap_or_default(),
+        ctx.get::<DefaultFlag>().unwrap_or_default(),
+    ];
+    ctx.state_mut::<Vec<String>>().unwrap().extend(values);
+    Ok(())
+}
+
+fn run_capture(app: &mut impl koral::traits::App, args: &[&str]) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    app.run_with_state(&mut out, args.iter().map(|s| s.to_string()).collect())
+        .unwrap();
+    out
+}
+
+#[test]
+fn test_parser_with_providers() {
+    let app = ProviderApp;
+    let parser = koral::internal::parser::Parser::new(app.flags())
+        .with_providers(vec![Box::new(VaultProvider)]);
+
+    let ctx = parser.parse(&[]).expect("Parse failed");
+    assert_eq!(
+        ctx.flags.get("envflag").unwrap().as_deref(),
+        Some("vault_value")
+    );
+    // Providers win over flag defaults
+    assert_eq!(
+        ctx.flags.get("defaultflag").unwrap().as_deref(),
+        Some("vault_default")
+    );
+
+    // The command line still wins over every provider
+    let args = vec!["--envflag".to_string(), "cli".to_string()];
+    let ctx = parser.parse(&args).expect("Parse failed");
+    assert_eq!(ctx.flags.get("envflag").unwrap().as_deref(), Some("cli"));
+}
+
+#[test]
+fn test_app_providers_attribute_sets_precedence() {
+    let out = run_capture(&mut VaultFirstApp, &["vault_first"]);
+    assert_eq!(out, vec!["vault_value", "vault_default"]);
+
+    let out = run_capture(&mut VaultFirstApp, &["vault_first", "--envflag", "cli"]);
+    assert_eq!(out, vec!["cli", "vault_default"]);
+}
+
+#[test]
+fn test_app_provider_field() {
+    let out = run_capture(&mut ProfileApp::default(), &["profile_app"]);
+    assert_eq!(out, vec!["profile_dev", "default_value"]);
+}
+
+#[derive(Default, App)]
+#[app(name = "inherit", action = capture)]
+#[app(flags(EnvFlag, DefaultFlag))]
+struct InheritCmd;
+
+#[derive(Default, App)]
+#[app(name = "own", action = capture, providers(EnvProvider))]
+#[app(flags(EnvFlag, DefaultFlag))]
+struct OwnCmd;
+
+#[derive(Subcommand)]
+enum VaultCommands {
+    Inherit(InheritCmd),
+    Own(OwnCmd),
+}
+
+impl Default for VaultCommands {
+    fn default() -> Self {
+        Self::Inherit(InheritCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "vault_root", providers(VaultProvider))]
+struct VaultRootApp {
+    #[app(subcommand)]
+    cmd: VaultCommands,
+}
+
+#[test]
+fn test_subcommands_inherit_providers() {
+    let run = |args: &[&str]| {
+        let mut out: Vec<String> = Vec::new();
+        koral::testing::Harness::new()
+            .env("KORAL_TEST_ENV", "env_value")
+            .run_with_state(&mut VaultRootApp::default(), &mut out, args.to_vec())
+            .assert_success();
+        out
+    };
+    assert_eq!(
+        run(&["vault_root", "inherit"]),
+        vec!["vault_value", "vault_default"]
+    );
+    // Providers of a subcommand replace the inherited ones
+    assert_eq!(
+        run(&["vault_root", "own"]),
+        vec!["env_value", "default_value"]
+    );
+}
+
+#[test]
+fn test_builder_provider() {
+    let mut app = koral::App::new("builder")
+        .register::<EnvFlag>()
+        .provider(VaultProvider)
+        .action(|ctx| {
+            assert_eq!(ctx.get::<EnvFlag>(), Some("vault_value".to_string()));
+            Ok(())
+        });
+    app.run(vec!["builder".to_string()]).unwrap();
+}
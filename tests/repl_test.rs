@@ -0,0 +1,158 @@
+use koral::extract::FlagVal;
+use koral::help::generate_help;
+use koral::prelude::*;
+use koral::repl::{CommandCompleter, LineReader, Repl};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+type Store = Arc<Mutex<HashMap<String, String>>>;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "upper", short = 'u')]
+struct UpperFlag(bool);
+
+fn set(args: Args, upper: FlagVal<UpperFlag>, store: State<Store>) -> KoralResult<()> {
+    let [key, value] = args.as_slice() else {
+        return Err(KoralError::MissingArgument("KEY VALUE".to_string()));
+    };
+    let value = if *upper {
+        value.to_uppercase()
+    } else {
+        value.clone()
+    };
+    store.lock().unwrap().insert(key.clone(), value);
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "set", action = set)]
+#[app(flags(UpperFlag))]
+struct SetCmd;
+
+#[derive(Default, App)]
+#[app(name = "get")]
+struct GetCmd;
+
+#[derive(Subcommand)]
+enum Commands {
+    Set(SetCmd),
+    Get(GetCmd),
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::Get(GetCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "kv", repl)]
+struct KvApp {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+/// Feeds a fixed list of lines to the session.
+struct Script(VecDeque<String>, Arc<Mutex<Vec<String>>>);
+
+impl Script {
+    fn new(lines: &[&str]) -> (Self, Arc<Mutex<Vec<String>>>) {
+        let history = Arc::new(Mutex::new(Vec::new()));
+        let lines = lines.iter().map(|s| s.to_string()).collect();
+        (Script(lines, history.clone()), history)
+    }
+}
+
+impl LineReader for Script {
+    fn read_line(&mut self, _prompt: &str) -> KoralResult<Option<String>> {
+        Ok(self.0.pop_front())
+    }
+
+    fn add_history(&mut self, line: &str) {
+        self.1.lock().unwrap().push(line.to_string());
+    }
+}
+
+fn session(lines: &[&str]) -> (HashMap<String, String>, Vec<String>) {
+    let (script, history) = Script::new(lines);
+    let mut store: Store = Arc::default();
+    let mut app = KvApp::default();
+    Repl::new(&mut app)
+        .reader(script)
+        .history_file(None)
+        .run_with_state(&mut store)
+        .unwrap();
+    let store = store.lock().unwrap().clone();
+    let history = history.lock().unwrap().clone();
+    (store, history)
+}
+
+#[test]
+fn test_state_persists_between_lines() {
+    let (store, history) = session(&[
+        "set name 'Ada Lovelace'",
+        "",
+        "set -u lang rust",
+        "set missing",
+        "set \"unterminated",
+        "help set",
+        "exit",
+        "set after exit",
+    ]);
+    assert_eq!(store.get("name").map(String::as_str), Some("Ada Lovelace"));
+    assert_eq!(store.get("lang").map(String::as_str), Some("RUST"));
+    assert_eq!(store.len(), 2);
+    assert_eq!(
+        history,
+        vec![
+            "set name 'Ada Lovelace'",
+            "set -u lang rust",
+            "set missing",
+            "help set",
+            "exit"
+        ]
+    );
+}
+
+#[test]
+fn test_session_ends_at_end_of_input() {
+    let (store, _) = session(&["set a 1"]);
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn test_shell_subcommand_starts_session() {
+    let help = generate_help(&KvApp::default());
+    assert!(help.contains("shell"));
+    assert!(help.contains("Start an interactive session"));
+}
+
+#[test]
+fn test_completion_follows_command_tree() {
+    let completer = CommandCompleter::new(&KvApp::default());
+    assert_eq!(completer.complete("s", 1), (0, vec!["set".to_string()]));
+    assert_eq!(
+        completer.complete("", 0).1,
+        vec!["exit", "get", "help", "quit", "set"]
+    );
+    let line = "set --u";
+    assert_eq!(
+        completer.complete(line, line.len()),
+        (4, vec!["--upper".to_string()])
+    );
+}
+
+#[cfg(not(feature = "repl"))]
+#[test]
+fn test_plain_session_uses_the_terminal() {
+    let mut store: Store = Arc::default();
+    let outcome = koral::testing::Harness::new()
+        .stdin("set a 1\nexit\n")
+        .run_with_state(&mut KvApp::default(), &mut store, ["kv", "shell"]);
+    outcome.assert_success();
+    assert_eq!(outcome.stdout, "kv> kv> ");
+    assert_eq!(
+        store.lock().unwrap().get("a").map(String::as_str),
+        Some("1")
+    );
+}
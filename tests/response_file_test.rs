@@ -0,0 +1,123 @@
+use koral::prelude::*;
+use koral::testing::Harness;
+use std::path::PathBuf;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "name", short = 'n')]
+struct NameFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "verbose")]
+struct VerboseFlag(bool);
+
+#[derive(Default, App)]
+#[app(name = "resp", action = capture, response_files)]
+#[app(flags(NameFlag, VerboseFlag))]
+struct RespApp;
+
+#[derive(Default, App)]
+#[app(name = "noresp", action = capture)]
+#[app(flags(NameFlag, VerboseFlag))]
+struct PlainApp;
+
+fn capture(mut ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "name={} verbose={} args={:?}",
+        ctx.get::<NameFlag>().unwrap_or_default(),
+        ctx.get::<VerboseFlag>().unwrap_or_default(),
+        ctx.args
+    );
+    ctx.state_mut::<Vec<String>>().unwrap().push(line);
+    Ok(())
+}
+
+fn run(app: &mut impl koral::traits::App, args: &[&str]) -> KoralResult<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    app.run_with_state(&mut out, args.iter().map(|s| s.to_string()).collect())?;
+    Ok(out)
+}
+
+fn response_file(name: &str, content: &str) -> String {
+    let dir: PathBuf = std::env::temp_dir().join(format!("koral-resp-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path.display().to_string()
+}
+
+#[test]
+fn test_expands_response_file() {
+    let path = response_file(
+        "basic.txt",
+        "# CI arguments\n--name \"John Doe\"\n--verbose\nfile.txt\n",
+    );
+    let out = run(&mut RespApp, &["resp", &format!("@{}", path), "last"]).unwrap();
+    assert_eq!(
+        out,
+        vec!["name=John Doe verbose=true args=[\"file.txt\", \"last\"]"]
+    );
+}
+
+#[test]
+fn test_nested_response_files() {
+    let inner = response_file("inner.txt", "-n inner\n");
+    let outer = response_file("outer.txt", &format!("@{}\n--verbose\n", inner));
+    let out = run(&mut RespApp, &["resp", &format!("@{}", outer)]).unwrap();
+    assert_eq!(out, vec!["name=inner verbose=true args=[]"]);
+}
+
+#[test]
+fn test_relative_response_file_uses_working_directory() {
+    let path = PathBuf::from(response_file("relative.txt", "-n relative\n"));
+    let cwd = path.parent().unwrap();
+
+    let mut out: Vec<String> = Vec::new();
+    Harness::new()
+        .cwd(cwd)
+        .run_with_state(&mut RespApp, &mut out, vec!["resp", "@relative.txt"])
+        .assert_success();
+    assert_eq!(out, vec!["name=relative verbose=false args=[]"]);
+}
+
+#[test]
+fn test_recursion_limit() {
+    let dir = std::env::temp_dir().join(format!("koral-resp-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("loop.txt").display().to_string();
+    std::fs::write(&path, format!("@{}\n", path)).unwrap();
+
+    let err = run(&mut RespApp, &["resp", &format!("@{}", path)]).unwrap_err();
+    match err {
+        KoralError::Validation(msg) => assert!(msg.contains("nested more than")),
+        other => panic!("Expected Validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_escape_and_double_dash() {
+    let out = run(&mut RespApp, &["resp", "@@literal", "--", "@not-a-file"]).unwrap();
+    assert_eq!(
+        out,
+        vec!["name= verbose=false args=[\"@literal\", \"@not-a-file\"]"]
+    );
+}
+
+#[test]
+fn test_missing_response_file() {
+    let err = run(&mut RespApp, &["resp", "@/nonexistent/koral/args.txt"]).unwrap_err();
+    assert!(matches!(err, KoralError::IoError(_)));
+}
+
+#[test]
+fn test_help_inside_response_file() {
+    let path = response_file("help.txt", "--help\n");
+    let out = run(&mut RespApp, &["resp", &format!("@{}", path)]).unwrap();
+    // Help was printed instead of running the action
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let out = run(&mut PlainApp, &["noresp", "@args.txt"]).unwrap();
+    assert_eq!(out, vec!["name= verbose=false args=[\"@args.txt\"]"]);
+}
@@ -0,0 +1,123 @@
+use koral::internal::parser::Parser;
+use koral::prelude::*;
+use koral::testing::Harness;
+use std::path::PathBuf;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "token", env = "SECRET_TEST_TOKEN", from_file, sensitive)]
+struct TokenFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "user")]
+struct UserFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "key", from_file)]
+struct KeyFlag(Vec<String>);
+
+#[derive(Default, App)]
+#[app(name = "secrets", action = report)]
+#[app(flags(TokenFlag))]
+struct SecretApp;
+
+fn report(ctx: Context) -> KoralResult<()> {
+    let source = ctx.source_of("token").map(|s| s.to_string());
+    ctx.terminal().print(&format!(
+        "{} ({})\n",
+        ctx.get::<TokenFlag>().unwrap_or_default(),
+        source.unwrap_or_default()
+    ))
+}
+
+fn parser() -> Parser {
+    Parser::new(vec![
+        FlagDef::from_trait::<TokenFlag>(),
+        FlagDef::from_trait::<UserFlag>(),
+        FlagDef::from_trait::<KeyFlag>(),
+    ])
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+fn secret_file(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("koral-secrets-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn test_env_file_convention() {
+    let path = secret_file("token", "from-secret-file\n");
+    let harness = Harness::new().env("SECRET_TEST_TOKEN_FILE", path.display().to_string());
+    harness
+        .run(&mut SecretApp, ["secrets"])
+        .assert_stdout_contains("from-secret-file (env SECRET_TEST_TOKEN_FILE)");
+
+    // The plain variable wins over the file
+    harness
+        .env("SECRET_TEST_TOKEN", "from-env")
+        .run(&mut SecretApp, ["secrets"])
+        .assert_stdout_contains("from-env (env SECRET_TEST_TOKEN)");
+}
+
+#[test]
+fn test_at_path_values() {
+    let path = secret_file("cli-token", "  abc123\n\n");
+    let key = secret_file("key", "k1\n");
+    let at_token = format!("@{}", path.display());
+    let at_key = format!("--key=@{}", key.display());
+
+    let ctx = parser()
+        .parse(&args(&["--token", &at_token, &at_key, "--key", "plain"]))
+        .unwrap();
+    assert_eq!(ctx.get::<TokenFlag>(), Some("abc123".to_string()));
+    assert_eq!(
+        ctx.get::<KeyFlag>(),
+        Some(vec!["k1".to_string(), "plain".to_string()])
+    );
+
+    // Flags without `from_file` keep the value as given
+    let ctx = parser().parse(&args(&["--user", &at_token])).unwrap();
+    assert_eq!(ctx.get::<UserFlag>(), Some(at_token.clone()));
+}
+
+#[test]
+fn test_missing_value_file_is_an_error() {
+    let err = parser()
+        .parse(&args(&["--token", "@/nonexistent/koral/token"]))
+        .unwrap_err();
+    assert!(matches!(err, KoralError::IoError(_)));
+
+    let outcome = Harness::new()
+        .env("SECRET_TEST_TOKEN_FILE", "/nonexistent/koral/token")
+        .run(&mut SecretApp, ["secrets"]);
+    assert!(matches!(outcome.result, Err(KoralError::IoError(_))));
+    outcome.assert_stderr_contains("SECRET_TEST_TOKEN_FILE");
+}
+
+#[test]
+fn test_sensitive_values_are_masked() {
+    let ctx = parser()
+        .parse(&args(&["--token", "hunter2", "--user", "alice"]))
+        .unwrap();
+
+    let debug = format!("{:?}", ctx);
+    assert!(!debug.contains("hunter2"));
+    assert!(debug.contains("********"));
+    assert!(debug.contains("alice"));
+
+    assert_eq!(
+        ctx.describe("token").as_deref(),
+        Some("token=******** (from command line)")
+    );
+    assert_eq!(
+        ctx.describe("user").as_deref(),
+        Some("user=alice (from command line)")
+    );
+    // The value itself is still available to the handler
+    assert_eq!(ctx.get::<TokenFlag>(), Some("hunter2".to_string()));
+}
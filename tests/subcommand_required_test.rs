@@ -20,7 +20,7 @@ enum Commands {
 
 impl Default for Commands {
     fn default() -> Self {
-        Self::Child(ChildCmd::default())
+        Self::Child(ChildCmd)
     }
 }
 
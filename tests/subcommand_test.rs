@@ -36,7 +36,7 @@ struct Level1App {
 impl Default for Level1App {
     fn default() -> Self {
         Self {
-            cmd: Level1Commands::Level2(Level2App::default()),
+            cmd: Level1Commands::Level2(Level2App),
         }
     }
 }
@@ -0,0 +1,190 @@
+use koral::prelude::*;
+use koral::testing::{self, assert_snapshot, Harness};
+use std::path::PathBuf;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Session {
+    user: String,
+}
+
+#[derive(Default)]
+struct AuthMiddleware;
+
+impl Middleware for AuthMiddleware {
+    fn before(&self, ctx: &mut Context) -> KoralResult<()> {
+        ctx.insert_extension(Session {
+            user: "alice".to_string(),
+        });
+        Ok(())
+    }
+}
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(
+    name = "name",
+    help = "Who to greet",
+    env = "HARNESS_TEST_NAME",
+    default = "world"
+)]
+struct NameFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "home", env = "HOME", default = "unset")]
+struct HomeFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "token", from_file)]
+struct TokenFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", help = "Region to deploy to", required = true)]
+struct RegionFlag(String);
+
+fn greet(
+    term: Terminal,
+    name: FlagArg<NameFlag>,
+    home: FlagArg<HomeFlag>,
+    token: Option<FlagArg<TokenFlag>>,
+) -> KoralResult<()> {
+    term.print(&format!("Hello, {}!\n", *name))?;
+    term.print(&format!("home={}\n", *home))?;
+    if let Some(token) = token {
+        term.print(&format!("token={}\n", *token))?;
+    }
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "greet", about = "Say hello", action = greet, config_file)]
+#[app(flags(NameFlag, HomeFlag, TokenFlag))]
+struct GreetApp;
+
+fn deploy(term: Terminal, region: FlagArg<RegionFlag>) -> KoralResult<()> {
+    term.print(&format!("deploying to {}\n", *region))
+}
+
+#[derive(Default, App)]
+#[app(name = "deploy", action = deploy, prompt_missing)]
+#[app(flags(RegionFlag))]
+struct DeployApp;
+
+fn whoami(term: Terminal, session: Extension<Session>) -> KoralResult<()> {
+    term.print(&format!("{}\n", session.user))
+}
+
+#[derive(Default, App)]
+#[app(name = "whoami", action = whoami, middleware(AuthMiddleware))]
+struct WhoamiApp;
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("koral-testing-{}", std::process::id()))
+        .join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_run_captures_output_and_exit_code() {
+    let outcome = testing::run(&mut GreetApp, ["greet", "--name", "koral"]);
+    outcome
+        .assert_success()
+        .assert_exit_code(0)
+        .assert_stdout_contains("Hello, koral!");
+    assert_eq!(outcome.stderr, "");
+
+    testing::run(&mut GreetApp, ["greet", "--name"])
+        .assert_failure()
+        .assert_exit_code(2)
+        .assert_stderr_contains("Error: Missing argument: Flag '--name' requires a value");
+}
+
+#[test]
+fn test_environment_is_faked() {
+    let outcome = Harness::new()
+        .env("HARNESS_TEST_NAME", "env")
+        .run(&mut GreetApp, ["greet"]);
+    // Variables of the process, such as HOME, are not visible
+    assert_eq!(outcome.stdout, "Hello, env!\nhome=unset\n");
+    assert!(std::env::var("HARNESS_TEST_NAME").is_err());
+}
+
+#[test]
+fn test_config_dir_and_cwd() {
+    let base = temp_dir("config");
+    std::fs::create_dir_all(base.join("greet")).unwrap();
+    std::fs::write(
+        base.join("greet").join("config.toml"),
+        "name = \"config\"\n",
+    )
+    .unwrap();
+    Harness::new()
+        .config_dir(&base)
+        .run(&mut GreetApp, ["greet"])
+        .assert_stdout_contains("Hello, config!");
+
+    let cwd = temp_dir("cwd");
+    std::fs::write(cwd.join("local.toml"), "name = \"local\"\n").unwrap();
+    std::fs::write(cwd.join("token.txt"), "from-file\n").unwrap();
+    Harness::new()
+        .cwd(&cwd)
+        .run(
+            &mut GreetApp,
+            ["greet", "--config", "local.toml", "--token", "@token.txt"],
+        )
+        .assert_stdout_contains("Hello, local!")
+        .assert_stdout_contains("token=from-file");
+}
+
+#[test]
+fn test_scripted_stdin() {
+    Harness::new()
+        .stdin("s3cret\n")
+        .run(&mut GreetApp, ["greet", "--token", "-"])
+        .assert_stdout_contains("token=s3cret");
+
+    let outcome = Harness::new()
+        .interactive(true)
+        .stdin("eu-west-1\n")
+        .run(&mut DeployApp, ["deploy"]);
+    outcome
+        .assert_success()
+        .assert_stderr_contains("Region to deploy to (--region): ");
+    assert_eq!(outcome.stdout, "deploying to eu-west-1\n");
+
+    // Nothing is asked without a terminal
+    testing::run(&mut DeployApp, ["deploy"])
+        .assert_exit_code(2)
+        .assert_stderr_contains("region");
+}
+
+#[test]
+fn test_extensions_are_kept() {
+    let outcome = testing::run(&mut WhoamiApp, ["whoami"]);
+    outcome.assert_success().assert_stdout_contains("alice");
+    assert_eq!(
+        outcome.extension::<Session>(),
+        Some(&Session {
+            user: "alice".to_string()
+        })
+    );
+}
+
+#[test]
+fn test_help_snapshot() {
+    testing::run(&mut GreetApp, ["greet", "--help"])
+        .assert_success()
+        .assert_stdout_snapshot("tests/snapshots/greet_help.txt");
+
+    let path = temp_dir("snapshots").join("help.txt");
+    let _ = std::fs::remove_file(&path);
+    // A missing snapshot is a failure, not a new snapshot
+    let missing = std::panic::catch_unwind(|| assert_snapshot(&path, "usage\n"));
+    assert!(missing.is_err());
+    assert!(!path.exists());
+
+    std::fs::write(&path, "usage\r\n").unwrap();
+    assert_snapshot(&path, "usage\n");
+    let mismatch = std::panic::catch_unwind(|| assert_snapshot(&path, "changed\n"));
+    assert!(mismatch.is_err());
+}
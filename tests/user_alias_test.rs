@@ -0,0 +1,163 @@
+use koral::prelude::*;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+type Seen = Arc<Mutex<Vec<String>>>;
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "output", short = 'o', default = "text", global)]
+struct OutputFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region")]
+struct RegionFlag(String);
+
+// A flag whose long name differs from its name
+struct ZoneFlag;
+impl Flag for ZoneFlag {
+    type Value = String;
+    fn name() -> &'static str {
+        "availability_zone"
+    }
+    fn long() -> Option<&'static str> {
+        Some("zone")
+    }
+    fn from_values(values: &[&str]) -> Result<String, String> {
+        String::from_last(values)
+    }
+}
+
+fn record(ctx: Context) -> KoralResult<()> {
+    let line = format!(
+        "{} output={} args={:?}",
+        ctx.inherited.path.join(" "),
+        ctx.get::<OutputFlag>().unwrap_or_default(),
+        ctx.args
+    );
+    ctx.state::<Seen>().unwrap().lock().unwrap().push(line);
+    Ok(())
+}
+
+#[derive(Default, App)]
+#[app(name = "list", action = record)]
+struct ListCmd;
+
+#[derive(Subcommand)]
+enum ClusterCommands {
+    List(ListCmd),
+}
+
+impl Default for ClusterCommands {
+    fn default() -> Self {
+        Self::List(ListCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "clusters")]
+struct ClustersCmd {
+    #[app(subcommand)]
+    cmd: ClusterCommands,
+}
+
+#[derive(Default, App)]
+#[app(name = "status", action = record)]
+struct StatusCmd;
+
+#[derive(Subcommand)]
+enum Commands {
+    Clusters(ClustersCmd),
+    Status(StatusCmd),
+}
+
+impl Default for Commands {
+    fn default() -> Self {
+        Self::Status(StatusCmd)
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "aliasapp", config_file)]
+#[app(flags(OutputFlag, RegionFlag, ZoneFlag))]
+struct AliasApp {
+    #[app(subcommand)]
+    cmd: Commands,
+}
+
+fn write_config(name: &str, content: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("koral-alias-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn run(config: &Path, args: &[&str]) -> KoralResult<Vec<String>> {
+    let mut seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    let mut full = vec!["aliasapp".to_string(), "--config".to_string()];
+    full.push(config.display().to_string());
+    full.extend(args.iter().map(|s| s.to_string()));
+    AliasApp::default().run_with_state(&mut seen, full)?;
+    let lines = seen.lock().unwrap().clone();
+    Ok(lines)
+}
+
+#[test]
+fn test_alias_expands_to_nested_command() {
+    let config = write_config(
+        "expand.toml",
+        "[alias]\nlsc = \"clusters list --output json\"\nlscx = [\"lsc\", \"extra arg\"]\n",
+    );
+    let out = run(&config, &["--region", "eu", "lsc"]).unwrap();
+    assert_eq!(out, vec!["clusters list output=json args=[]"]);
+
+    let out = run(&config, &["lscx", "more"]).unwrap();
+    assert_eq!(
+        out,
+        vec!["clusters list output=json args=[\"extra arg\", \"more\"]"]
+    );
+}
+
+#[test]
+fn test_alias_from_ini_config() {
+    let config = write_config("expand.ini", "[alias]\nst = status -o 'wide table'\n");
+    let out = run(&config, &["st"]).unwrap();
+    assert_eq!(out, vec!["status output=wide table args=[]"]);
+}
+
+#[test]
+fn test_alias_after_renamed_long_flag() {
+    let config = write_config("renamed.toml", "[alias]\nst = \"status -o wide\"\n");
+    let out = run(&config, &["--zone", "st", "st"]).unwrap();
+    assert_eq!(out, vec!["status output=wide args=[]"]);
+}
+
+#[test]
+fn test_recursive_alias_is_an_error() {
+    let config = write_config("recursive.toml", "[alias]\na = \"b\"\nb = \"-o json a\"\n");
+    let err = run(&config, &["a"]).unwrap_err();
+    assert!(matches!(err, KoralError::Validation(ref msg) if msg.contains("a -> b -> a")));
+}
+
+#[test]
+fn test_alias_cannot_shadow_builtin_command() {
+    let config = write_config("shadow.toml", "[alias]\nstatus = \"clusters list\"\n");
+    let out = run(&config, &["status"]).unwrap();
+    assert_eq!(out, vec!["status output=text args=[]"]);
+}
+
+#[test]
+fn test_broken_config_does_not_break_help() {
+    for (name, content) in [
+        ("malformed.toml", "[alias\nlsc = \n"),
+        ("recursive-help.toml", "[alias]\na = \"b\"\nb = \"a\"\n"),
+    ] {
+        let config = write_config(name, content);
+        let config = config.display().to_string();
+        let args = ["aliasapp", "--config", config.as_str(), "a"];
+        koral::testing::run(&mut AliasApp::default(), args).assert_failure();
+        koral::testing::run(&mut AliasApp::default(), [&args[..], &["--help"]].concat())
+            .assert_success()
+            .assert_stdout_contains("Usage: aliasapp");
+    }
+}
@@ -0,0 +1,135 @@
+use koral::config::ConfigFormat;
+use koral::prelude::*;
+use koral::provider::{FileProvider, ValueProvider};
+use koral::testing::Harness;
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "region", env = "SOURCE_TEST_REGION", default = "local")]
+struct RegionFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "zone")]
+struct ZoneFlag(String);
+
+#[derive(Flag, Debug, PartialEq)]
+#[flag(name = "token")]
+struct TokenFlag(String);
+
+#[derive(Default)]
+struct Vault;
+
+impl ValueProvider for Vault {
+    fn get_value(&self, flag: &FlagDef) -> Option<String> {
+        (flag.name == "token").then(|| "s3cr3t".to_string())
+    }
+}
+
+fn audit_log() -> &'static Mutex<Vec<String>> {
+    static LOG: OnceLock<Mutex<Vec<String>>> = OnceLock::new();
+    LOG.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+#[derive(Default)]
+struct AuditMiddleware;
+
+impl Middleware for AuditMiddleware {
+    fn before(&self, ctx: &mut Context) -> KoralResult<()> {
+        let region = ctx.get::<RegionFlag>().unwrap_or_default();
+        let source = ctx.source_of("region").unwrap();
+        audit_log()
+            .lock()
+            .unwrap()
+            .push(format!("region={} (from {})", region, source));
+        Ok(())
+    }
+}
+
+#[derive(Default, App)]
+#[app(name = "audited", action = audited)]
+#[app(flags(RegionFlag, ZoneFlag))]
+#[app(middleware(AuditMiddleware))]
+struct AuditedApp;
+
+fn audited(region: Sourced<FlagArg<RegionFlag>>) -> KoralResult<()> {
+    audit_log()
+        .lock()
+        .unwrap()
+        .push(format!("handler {} {:?}", **region, region.source));
+    Ok(())
+}
+
+fn parser(flags: Vec<FlagDef>) -> koral::internal::parser::Parser {
+    koral::internal::parser::Parser::new(flags)
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_sources_recorded_by_parser() {
+    let flags = vec![
+        FlagDef::from_trait::<RegionFlag>(),
+        FlagDef::from_trait::<ZoneFlag>(),
+    ];
+
+    let ctx = parser(flags.clone())
+        .parse(&args(&["--zone", "b"]))
+        .unwrap();
+    assert_eq!(ctx.source_of("zone"), Some(ValueSource::CommandLine));
+    assert_eq!(ctx.source_of("region"), Some(ValueSource::Default));
+
+    let ctx = parser(flags).parse(&[]).unwrap();
+    assert_eq!(ctx.source_of("zone"), None);
+}
+
+#[test]
+fn test_config_file_and_custom_provider_sources() {
+    let config =
+        FileProvider::parse("region = \"eu-1\"\nzone = \"c\"\n", ConfigFormat::Ini).unwrap();
+    let flags = vec![
+        FlagDef::from_trait::<RegionFlag>(),
+        FlagDef::from_trait::<ZoneFlag>(),
+        FlagDef::from_trait::<TokenFlag>(),
+    ];
+
+    let ctx = parser(flags)
+        .with_providers(vec![Box::new(Vault)])
+        .config(Some(config))
+        .parse(&args(&["--zone", "a"]))
+        .unwrap();
+
+    // Explicit values win over the config file
+    assert_eq!(ctx.get::<ZoneFlag>(), Some("a".to_string()));
+    assert_eq!(ctx.source_of("zone"), Some(ValueSource::CommandLine));
+    assert_eq!(ctx.get::<RegionFlag>(), Some("eu-1".to_string()));
+    assert_eq!(ctx.source_of("region"), Some(ValueSource::ConfigFile(None)));
+    assert_eq!(
+        ctx.source_of("token"),
+        Some(ValueSource::Provider("Vault".to_string()))
+    );
+}
+
+#[test]
+fn test_audit_middleware_and_sourced_extractor() {
+    audit_log().lock().unwrap().clear();
+
+    Harness::new()
+        .env("SOURCE_TEST_REGION", "us-east-1")
+        .run(&mut AuditedApp, ["audited"])
+        .assert_success();
+    AuditedApp
+        .run(args(&["audited", "--region", "ap-2"]))
+        .unwrap();
+
+    assert_eq!(
+        *audit_log().lock().unwrap(),
+        vec![
+            "region=us-east-1 (from env SOURCE_TEST_REGION)",
+            "handler us-east-1 Env(\"SOURCE_TEST_REGION\")",
+            "region=ap-2 (from command line)",
+            "handler ap-2 CommandLine",
+        ]
+    );
+}